@@ -1,21 +1,32 @@
+use crate::cache;
 use crate::event_date::EventDate;
+use crate::html;
+use crate::problem::Problem;
+use crate::profile::Profile;
+use crate::submission::{Cooldown, SubmissionVerdict};
 use anyhow::{Result, anyhow};
 use regex::Regex;
-use reqwest::{header, redirect};
+use reqwest::{blocking::Response, header, redirect};
 use std::env;
 use std::fmt::Display;
 use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
-const AOC_URL: &str = "https://adventofcode.com";
-const AOC_AUTH_TOKEN: &str = "AOC_AUTH_TOKEN";
-const CACHE_DIR: &str = env!("AOC_CACHE_DIR");
+pub const DEFAULT_AOC_URL: &str = "https://adventofcode.com";
+const AOC_URL: &str = "AOC_URL";
+/// Stars only change when we submit, which expires the cached calendar, so it
+/// can be cached briefly
+const CALENDAR_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+/// AoC asks that private leaderboards be polled at most once every 15 minutes
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
 
 #[derive(Clone, Copy)]
 enum Extension {
     Txt,
     Html,
+    Json,
 }
 
 impl Display for Extension {
@@ -23,6 +34,7 @@ impl Display for Extension {
         let s = match &self {
             Extension::Txt => "txt",
             Extension::Html => "html",
+            Extension::Json => "json",
         };
         f.write_str(s)
     }
@@ -32,116 +44,282 @@ struct CacheOptions<'a> {
     resource: &'a String,
     extension: Extension,
     bust_cache: bool,
+    /// How long a cached copy stays valid for, forever if `None`
+    max_age: Option<Duration>,
+    profile: &'a Profile,
 }
 
-fn get_client() -> reqwest::blocking::Client {
+/// The base URL requests are made against, `AOC_URL` overrides the real site
+/// (e.g. to point at a `mock_aoc` server, built with `--features mock`).
+fn base_url() -> Result<reqwest::Url> {
+    let url = env::var(AOC_URL).unwrap_or_else(|_| DEFAULT_AOC_URL.to_string());
+    Ok(reqwest::Url::from_str(&url)?)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("{0} is not set, copy the session cookie from adventofcode.com into it")]
+    MissingToken(String),
+    #[error("The session token in {0} has expired or is invalid, log in again and update it")]
+    Expired(String),
+}
+
+fn get_client(profile: &Profile) -> Result<reqwest::blocking::Client> {
+    let auth_token_var = profile.auth_token_var();
     let auth_token =
-        env::var(AOC_AUTH_TOKEN).expect(&format!("Expected {} to be set", AOC_AUTH_TOKEN));
+        env::var(&auth_token_var).map_err(|_| AuthError::MissingToken(auth_token_var))?;
     let mut headers = header::HeaderMap::with_capacity(1);
-    let cookie: header::HeaderValue = format!("session={auth_token}").parse().unwrap();
-    headers.try_insert(header::COOKIE, cookie).unwrap();
-    reqwest::blocking::ClientBuilder::new()
+    let cookie: header::HeaderValue = format!("session={auth_token}").parse()?;
+    headers.try_insert(header::COOKIE, cookie)?;
+    let client = reqwest::blocking::ClientBuilder::new()
         .user_agent("aot (+https://github.com/EthanOlpin)")
         .default_headers(headers)
         .redirect(redirect::Policy::none())
-        .build()
-        .unwrap()
+        .build()?;
+    Ok(client)
 }
 
-fn get(path: &str) -> Result<String> {
-    let url = reqwest::Url::from_str(AOC_URL)?.join(path)?;
-    let response = get_client().get(url).send()?;
-    let result = response.error_for_status()?;
-    let text = result.text()?.trim_end_matches('\n').to_string();
-    Ok(text)
+/// Logged out visitors get a "[Log In]" link in the page header, or are told to
+/// log in outright when requesting an input.
+fn is_login_page(body: &str) -> bool {
+    body.contains("auth/login\"") || body.contains("Please log in")
 }
 
-fn post(path: &str, body: &str) -> Result<String> {
-    let url = reqwest::Url::from_str(AOC_URL)?.join(path)?;
-    let response = get_client()
-        .post(url)
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(body.to_string())
-        .send()?;
-    if response.status() == 303 {
+fn read_response(profile: &Profile, request: &str, response: Response) -> Result<String> {
+    if response.status().is_redirection() {
         let location = response
             .headers()
             .get("location")
             .and_then(|location| location.to_str().ok())
             .unwrap_or("unknown");
-
-        return Err(anyhow!("POST {path} would redirect to {location}"));
+        if location.contains("auth/login") {
+            return Err(AuthError::Expired(profile.auth_token_var()).into());
+        }
+        return Err(anyhow!("{request} would redirect to {location}"));
+    }
+    let status = response.status();
+    let text = response.text()?;
+    if is_login_page(&text) {
+        return Err(AuthError::Expired(profile.auth_token_var()).into());
+    }
+    if !status.is_success() {
+        return Err(anyhow!("{request} failed with {status}"));
     }
-    let result = response.error_for_status()?;
-    let text = result.text()?;
     Ok(text)
 }
 
+fn get(profile: &Profile, path: &str) -> Result<String> {
+    let url = base_url()?.join(path)?;
+    let response = get_client(profile)?.get(url).send()?;
+    let text = read_response(profile, &format!("GET {path}"), response)?;
+    Ok(text.trim_end_matches('\n').to_string())
+}
+
+fn post(path: &str, form: &[(&str, &str)]) -> Result<String> {
+    let profile = Profile::selected();
+    let url = base_url()?.join(path)?;
+    let response = get_client(profile)?.post(url).form(form).send()?;
+    read_response(profile, &format!("POST {path}"), response)
+}
+
+/// Bodies AoC serves in place of a problem or input that must never be cached.
+const ERROR_BODIES: [&str; 2] = [
+    "Please don't repeatedly request this endpoint before it unlocks!",
+    "404 Not Found",
+];
+
+/// Whether `body` is an HTML document. Only the doctype or root element count,
+/// plain text inputs can start with `<` too (e.g. 2015 day 3's `^v<>` moves).
+fn is_html(body: &str) -> bool {
+    let start = body.trim_start().as_bytes();
+    ["<!doctype", "<html"].iter().any(|prefix| {
+        start
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix.as_bytes()))
+    })
+}
+
+/// Why `body` can't be a real problem, input or leaderboard, if it's one of
+/// AoC's error or login pages. `text` is set for plain text resources (inputs).
+pub fn error_page_reason(body: &str, text: bool) -> Option<String> {
+    if let Some(error) = ERROR_BODIES
+        .iter()
+        .find(|error| body.trim_start().starts_with(*error))
+    {
+        return Some(format!("AoC responded with: {error}"));
+    }
+    if is_login_page(body) {
+        return Some("AoC responded with a login page".to_string());
+    }
+    if text && is_html(body) {
+        return Some("expected text but got HTML".to_string());
+    }
+    None
+}
+
+fn validate(resource: &str, extension: Extension, body: &str) -> Result<()> {
+    match error_page_reason(body, matches!(extension, Extension::Txt)) {
+        Some(reason) => Err(anyhow!("Refusing to cache {resource}, {reason}")),
+        None => Ok(()),
+    }
+}
+
+fn cache_path(profile: &Profile, resource: &str, extension: Extension) -> Result<PathBuf> {
+    Ok(profile.cache_dir()?.join(format!("{resource}.{extension}")))
+}
+
 fn cached<F: FnOnce() -> Result<String>>(options: CacheOptions, f: F) -> Result<String> {
     let CacheOptions {
         resource,
         extension,
         bust_cache,
+        max_age,
+        profile,
     } = options;
-    let cache_path = Path::new(CACHE_DIR).join(format!("{resource}.{extension}"));
-    if !bust_cache && cache_path.exists() {
+    let cache_path = cache_path(profile, resource, extension)?;
+    let fresh = cache_path.exists()
+        && match max_age {
+            Some(max_age) => fs::metadata(&cache_path)?
+                .modified()?
+                .elapsed()
+                .is_ok_and(|age| age < max_age),
+            None => true,
+        };
+    if !bust_cache && fresh {
         let cached_contents = fs::read_to_string(cache_path)?;
         return Ok(cached_contents);
     }
 
     let result = f()?;
+    validate(resource, extension, &result)?;
 
     if let Some(cache_dir) = cache_path.parent() {
         fs::create_dir_all(cache_dir)?;
-        fs::write(cache_path, &result)?;
+        cache::write_atomically(&cache_path, &result)?;
     }
     Ok(result)
 }
 
-fn _get_problem(date: &EventDate, bust_cache: bool) -> Result<String> {
+fn _get_problem(date: &EventDate, profile: &Profile, bust_cache: bool) -> Result<String> {
     let resource = format!("{}/day/{}", date.year, date.day);
     let options = CacheOptions {
         resource: &resource,
         extension: Extension::Html,
         bust_cache,
+        max_age: None,
+        profile,
     };
-    cached(options, || get(&resource))
+    cached(options, || {
+        date.ensure_unlocked()?;
+        get(profile, &resource)
+    })
 }
 
 pub fn get_problem(date: &EventDate) -> Result<String> {
-    _get_problem(&date, false)
+    _get_problem(date, Profile::selected(), false)
+}
+/// The problem as `profile` sees it, including the answers it got accepted.
+pub fn get_problem_for(date: &EventDate, profile: &Profile) -> Result<String> {
+    _get_problem(date, profile, false)
 }
 pub fn get_refreshed_problem(date: &EventDate) -> Result<String> {
-    _get_problem(date, true)
+    _get_problem(date, Profile::selected(), true)
+}
+
+pub fn is_problem_cached(date: &EventDate) -> bool {
+    let resource = format!("{}/day/{}", date.year, date.day);
+    cache_path(Profile::selected(), &resource, Extension::Html).is_ok_and(|path| path.exists())
+}
+
+pub fn is_input_cached(date: &EventDate) -> bool {
+    let resource = format!("{}/day/{}/input", date.year, date.day);
+    cache_path(Profile::selected(), &resource, Extension::Txt).is_ok_and(|path| path.exists())
 }
 
 pub fn get_input(date: &EventDate) -> Result<String> {
+    get_input_for(date, Profile::selected())
+}
+
+pub fn get_input_for(date: &EventDate, profile: &Profile) -> Result<String> {
     let resource = format!("{}/day/{}/input", date.year, date.day);
     let options = CacheOptions {
         resource: &resource,
         extension: Extension::Txt,
         bust_cache: false,
+        max_age: None,
+        profile,
     };
-    cached(options, || get(&resource))
+    cached(options, || {
+        date.ensure_unlocked()?;
+        get(profile, &resource)
+    })
 }
 
-pub fn post_answer(date: &EventDate, part: u8, answer: String) -> Result<()> {
-    let body = format!("level={part}&answer={answer}");
-    let resource = format!("{}/day/{}/answer", date.year, date.day);
-    let response = post(&resource, &body)?;
-    if response.contains("That's the right answer") {
-        return Ok(());
-    } else {
-        let message = Regex::new(r"<main>(?s:(?P<main>.*))</main>")
-            .unwrap()
-            .captures(&response)
-            .map(|captures| captures.name("main").unwrap().as_str().to_string())
-            .unwrap_or_else(|| format!("Full response: {response}"));
-        return Err(anyhow!("Submission rejected: {message}"));
-    }
+pub fn get_private_leaderboard(year: u16, id: u64) -> Result<String> {
+    let resource = format!("{year}/leaderboard/private/view/{id}");
+    let options = CacheOptions {
+        resource: &resource,
+        extension: Extension::Json,
+        bust_cache: false,
+        max_age: Some(LEADERBOARD_MAX_AGE),
+        profile: Profile::selected(),
+    };
+    cached(options, || {
+        get(Profile::selected(), &format!("{resource}.json"))
+    })
+}
+
+/// The `/{year}` calendar page, showing the stars earned each day.
+pub fn get_calendar(year: u16) -> Result<String> {
+    let resource = year.to_string();
+    let options = CacheOptions {
+        resource: &resource,
+        extension: Extension::Html,
+        bust_cache: false,
+        max_age: Some(CALENDAR_MAX_AGE),
+        profile: Profile::selected(),
+    };
+    cached(options, || get(Profile::selected(), &resource))
 }
 
-pub fn refresh_problem(date: &EventDate) -> Result<()> {
-    _get_problem(date, true)?;
+/// Drops the cached calendar for `year`, so the stars from a correct answer
+/// show up right away.
+fn expire_calendar(year: u16) -> Result<()> {
+    let path = cache_path(Profile::selected(), &year.to_string(), Extension::Html)?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
     Ok(())
 }
+
+/// The display name of the logged in user, erroring if the session token is
+/// missing or no longer valid.
+pub fn get_user(profile: &Profile) -> Result<String> {
+    let settings = get(profile, "settings")?;
+    let user_re = Regex::new(r#"<div class="user">([^<]*)"#)?;
+    let user = user_re
+        .captures(&settings)
+        .map(|caps| html::unescape(caps[1].trim()))
+        .ok_or(AuthError::Expired(profile.auth_token_var()))?;
+    Ok(user)
+}
+
+pub fn post_answer(date: &EventDate, part: u8, answer: String) -> Result<SubmissionVerdict> {
+    let resource = format!("{}/day/{}/answer", date.year, date.day);
+    let level = part.to_string();
+    let response = post(&resource, &[("level", &level), ("answer", &answer)])?;
+    let verdict = SubmissionVerdict::parse(&response)?;
+    if let Some(wait) = verdict.cooldown(&response)? {
+        Cooldown::start(wait)?;
+    }
+    if verdict == SubmissionVerdict::Correct {
+        expire_calendar(date.year)?;
+    }
+    if verdict == SubmissionVerdict::WrongLevel {
+        let problem_html = get_refreshed_problem(date)?;
+        if Problem::parse(&problem_html).answers.len() >= part.into() {
+            return Ok(SubmissionVerdict::AlreadyCompleted);
+        }
+    }
+    Ok(verdict)
+}
@@ -0,0 +1,348 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+
+use crate::solution::PartFn;
+
+/// Enough samples for stable statistics without keeping millions of them
+/// for solutions that take nanoseconds.
+pub const MAX_SAMPLES: usize = 100_000;
+
+/// A solution's input parser, run on its own to time the parse phase.
+pub type ParseFn = fn(String);
+
+/// How long to benchmark each phase for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    Iterations(u32),
+    Time(Duration),
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::Time(Duration::from_secs(3))
+    }
+}
+
+impl Budget {
+    /// The arguments passing this budget to a solution binary.
+    pub fn to_args(&self) -> [String; 2] {
+        match self {
+            Budget::Iterations(iterations) => ["--iterations".to_string(), iterations.to_string()],
+            Budget::Time(time) => ["--time".to_string(), format!("{}ns", time.as_nanos())],
+        }
+    }
+}
+
+/// Parses durations like `5s`, `1.5s`, `500ms`, `250us`, `100ns` or `2m`.
+pub fn parse_duration(s: &str) -> anyhow::Result<Duration> {
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .ok_or(anyhow!("{s} is missing a unit, e.g. 5s or 500ms"))?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| anyhow!("{s} is not a valid duration"))?;
+    let seconds = match unit {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" => value,
+        "m" => value * 60.0,
+        _ => {
+            return Err(anyhow!(
+                "Unknown unit {unit} in {s}, expected ns, us, ms, s or m"
+            ));
+        }
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow!("{s} is too long a duration"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum Phase {
+    Parse,
+    /// The whole part, parsing included
+    Solve,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve => write!(f, "solve"),
+        }
+    }
+}
+
+/// The time each iteration of a phase took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub phase: Phase,
+    pub samples: Vec<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, bincode::Encode, bincode::Decode)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        // Nearest-rank percentile
+        let percentile = |p: f64| sorted[((p * runs as f64).ceil() as usize).clamp(1, runs) - 1];
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (runs.max(2) - 1) as f64;
+        Some(Stats {
+            runs,
+            min: sorted[0],
+            median: percentile(0.5),
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95: percentile(0.95),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} runs, min {:.2?}, median {:.2?}, mean {:.2?} ± {:.2?}, p95 {:.2?}",
+            self.runs, self.min, self.median, self.mean, self.stddev, self.p95
+        )
+    }
+}
+
+/// The p-value below which a change in mean time is taken to be real.
+pub const SIGNIFICANCE: f64 = 0.05;
+
+/// How a benchmark compares with an earlier one of the same phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// The current mean time over the baseline's
+    pub ratio: f64,
+    /// The chance of a difference in means this large if nothing changed,
+    /// from Welch's t-test
+    pub p_value: f64,
+}
+
+impl Comparison {
+    pub fn new(current: &Stats, baseline: &Stats) -> Self {
+        Comparison {
+            ratio: current.mean.as_secs_f64() / baseline.mean.as_secs_f64(),
+            p_value: welch_p_value(current, baseline),
+        }
+    }
+
+    pub fn significant(&self) -> bool {
+        self.p_value < SIGNIFICANCE
+    }
+
+    /// How much slower the current run is, as a percentage of the baseline.
+    pub fn slowdown_percent(&self) -> f64 {
+        (self.ratio - 1.0) * 100.0
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.significant() {
+            write!(f, "no change")?;
+        } else if self.ratio < 1.0 {
+            write!(f, "{:.2}x faster", 1.0 / self.ratio)?;
+        } else {
+            write!(f, "{:.2}x slower", self.ratio)?;
+        }
+        write!(f, " (p = {:.3})", self.p_value)
+    }
+}
+
+/// The two-sided p-value of Welch's t-test for a difference in mean.
+fn welch_p_value(a: &Stats, b: &Stats) -> f64 {
+    if a.runs < 2 || b.runs < 2 {
+        return 1.0;
+    }
+    let error = |stats: &Stats| stats.stddev.as_secs_f64().powi(2) / stats.runs as f64;
+    let (error_a, error_b) = (error(a), error(b));
+    let difference = a.mean.as_secs_f64() - b.mean.as_secs_f64();
+    if error_a + error_b == 0.0 {
+        return if difference == 0.0 { 1.0 } else { 0.0 };
+    }
+    let t = difference / (error_a + error_b).sqrt();
+    let degrees_of_freedom = (error_a + error_b).powi(2)
+        / (error_a.powi(2) / (a.runs - 1) as f64 + error_b.powi(2) / (b.runs - 1) as f64);
+    incomplete_beta(
+        degrees_of_freedom / 2.0,
+        0.5,
+        degrees_of_freedom / (degrees_of_freedom + t * t),
+    )
+}
+
+/// The natural log of the gamma function, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_403,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .zip(1..)
+        .fold(COEFFICIENTS[0], |sum, (c, i)| sum + c / (x + i as f64));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// The regularized incomplete beta function `I_x(a, b)`.
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // The continued fraction converges quickly on this side of the mean
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Evaluates the continued fraction for the incomplete beta function with
+/// Lentz's method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let clamp = |value: f64| if value.abs() < TINY { TINY } else { value };
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut result = d;
+    for m in 1..200 {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / clamp(1.0 + even * d);
+        c = clamp(1.0 + even / c);
+        result *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / clamp(1.0 + odd * d);
+        c = clamp(1.0 + odd / c);
+        let step = d * c;
+        result *= step;
+        if (step - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    result
+}
+
+/// Runs `run` on fresh values from `setup` until the budget is spent, timing
+/// only `run`. The first tenth of the budget warms up caches and branch
+/// predictors and isn't recorded. Timed budgets stop early after
+/// [`MAX_SAMPLES`] iterations.
+pub fn measure<T>(
+    budget: Budget,
+    mut setup: impl FnMut() -> T,
+    mut run: impl FnMut(T) -> anyhow::Result<()>,
+) -> anyhow::Result<Vec<Duration>> {
+    let mut iterate = |samples: &mut Vec<Duration>| -> anyhow::Result<()> {
+        let value = setup();
+        let start = Instant::now();
+        run(value)?;
+        samples.push(start.elapsed());
+        Ok(())
+    };
+
+    let mut samples = Vec::new();
+    match budget {
+        Budget::Iterations(iterations) => {
+            for _ in 0..(iterations / 10).max(1) {
+                iterate(&mut samples)?;
+            }
+            samples.clear();
+            for _ in 0..iterations.max(1) {
+                iterate(&mut samples)?;
+            }
+        }
+        Budget::Time(time) => {
+            let start = Instant::now();
+            while samples.is_empty() || start.elapsed() < time / 10 && samples.len() < MAX_SAMPLES {
+                iterate(&mut samples)?;
+            }
+            samples.clear();
+            let start = Instant::now();
+            while samples.is_empty() || start.elapsed() < time && samples.len() < MAX_SAMPLES {
+                iterate(&mut samples)?;
+            }
+        }
+    }
+    Ok(samples)
+}
+
+/// Benchmarks the parse phase, when the solution exposes one, and then the
+/// whole part.
+pub fn bench_part(
+    solve: PartFn,
+    parse: Option<ParseFn>,
+    input: &str,
+    budget: Budget,
+) -> anyhow::Result<Vec<Measurement>> {
+    let mut measurements = Vec::new();
+    if let Some(parse) = parse {
+        let samples = measure(
+            budget,
+            || input.to_string(),
+            |input| {
+                parse(input);
+                Ok(())
+            },
+        )?;
+        measurements.push(Measurement {
+            phase: Phase::Parse,
+            samples,
+        });
+    }
+    let samples = measure(
+        budget,
+        || input.to_string(),
+        |input| {
+            std::hint::black_box(solve(input)?);
+            Ok(())
+        },
+    )?;
+    measurements.push(Measurement {
+        phase: Phase::Solve,
+        samples,
+    });
+    Ok(measurements)
+}
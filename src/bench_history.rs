@@ -0,0 +1,163 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::Utc;
+
+use crate::{
+    bench::{Phase, Stats},
+    cache,
+    event_date::EventDate,
+    solution::Part,
+};
+
+/// Where `aor bench` keeps its results, next to the solutions.
+pub const HISTORY_PATH: &str = "bench_history.bin";
+
+#[derive(Debug, Clone, PartialEq, bincode::Encode, bincode::Decode)]
+pub struct BenchRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub phase: Phase,
+    /// The checked out commit, if the solutions are in a git repository
+    pub commit: Option<String>,
+    /// A hash of the solution's source, to tell runs of the same code apart
+    pub source_hash: String,
+    /// Unix timestamp of when the benchmark ran
+    pub recorded_at: i64,
+    pub stats: Stats,
+}
+
+impl BenchRecord {
+    pub fn new(
+        date: &EventDate,
+        part: Part,
+        phase: Phase,
+        commit: Option<String>,
+        source_hash: String,
+        stats: Stats,
+    ) -> Self {
+        BenchRecord {
+            year: date.year,
+            day: date.day,
+            part: part.to_int(),
+            phase,
+            commit,
+            source_hash,
+            recorded_at: Utc::now().timestamp(),
+            stats,
+        }
+    }
+
+    fn same_benchmark(&self, other: &BenchRecord) -> bool {
+        (self.year, self.day, self.part, self.phase)
+            == (other.year, other.day, other.part, other.phase)
+    }
+}
+
+/// What to compare a benchmark with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Baseline {
+    /// The latest earlier run
+    Previous,
+    /// The latest run at a commit starting with this hash
+    Commit(String),
+}
+
+/// Every benchmark result recorded in this repository, oldest first.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct BenchHistory {
+    pub records: Vec<BenchRecord>,
+}
+
+impl BenchHistory {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(BenchHistory::default());
+        }
+        let bytes = fs::read(path)?;
+        let (history, _) = bincode::decode_from_slice(&bytes, bincode::config::standard())?;
+        Ok(history)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        cache::write_atomically(
+            path,
+            bincode::encode_to_vec(self, bincode::config::standard())?,
+        )?;
+        Ok(())
+    }
+
+    /// The latest recorded run of the same benchmark as `record` to compare it
+    /// with. At a commit, runs of the source as committed (`committed_hash`)
+    /// win over later ones that benchmarked uncommitted edits.
+    pub fn baseline(
+        &self,
+        record: &BenchRecord,
+        baseline: &Baseline,
+        committed_hash: Option<&str>,
+    ) -> Option<&BenchRecord> {
+        let mut candidates = self.records.iter().rev().filter(|earlier| {
+            earlier.same_benchmark(record)
+                && match baseline {
+                    Baseline::Previous => true,
+                    Baseline::Commit(commit) => earlier
+                        .commit
+                        .as_ref()
+                        .is_some_and(|earlier| earlier.starts_with(commit.as_str())),
+                }
+        });
+        match (baseline, committed_hash) {
+            (Baseline::Commit(_), Some(committed_hash)) => {
+                let candidates = candidates.collect::<Vec<_>>();
+                candidates
+                    .iter()
+                    .find(|earlier| earlier.source_hash == committed_hash)
+                    .or(candidates.first())
+                    .copied()
+            }
+            _ => candidates.next(),
+        }
+    }
+}
+
+pub fn history_path() -> PathBuf {
+    PathBuf::from(HISTORY_PATH)
+}
+
+/// The short hash of the checked out commit, `None` outside a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The [`source_hash`] of `path` as committed at `commit`, `None` if git
+/// doesn't know either.
+pub fn committed_source_hash(commit: &str, path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!("{commit}:./{}", path.display()))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(source_hash(&output.stdout))
+}
+
+/// A 64-bit FNV-1a hash of `source`, stable across builds unlike `std`'s hasher.
+pub fn source_hash(source: &[u8]) -> String {
+    let hash = source.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
@@ -0,0 +1,158 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+
+use crate::{
+    aoc_client,
+    config::{self, Config},
+    event_date::EventDate,
+    submission::SubmissionHistory,
+};
+
+const AOC_CACHE_DIR: &str = "AOC_CACHE_DIR";
+
+/// The directory problems, inputs and submission history are cached in. Taken
+/// from `AOC_CACHE_DIR`, then `cache_dir` in the config file, defaulting to
+/// `$XDG_CACHE_HOME/aor` (`~/.cache/aor`). Named profiles keep their own cache
+/// inside it, see [`crate::profile::Profile::cache_dir`].
+pub fn dir() -> anyhow::Result<PathBuf> {
+    let dir = match env::var_os(AOC_CACHE_DIR) {
+        Some(dir) => PathBuf::from(dir),
+        None => match Config::load()?.cache_dir {
+            Some(dir) => dir,
+            None => config::xdg_dir("CACHE", ".cache")
+                .ok_or(anyhow!(
+                    "Can't find a cache directory, set {AOC_CACHE_DIR} or HOME"
+                ))?
+                .join("aor"),
+        },
+    };
+    if dir.exists() && !dir.is_dir() {
+        return Err(anyhow!(
+            "Cache directory {} is not a directory",
+            dir.display()
+        ));
+    }
+    Ok(dir)
+}
+
+/// Writes to a temporary file next to `path` and renames it into place, so an
+/// interrupted write never leaves a truncated file behind.
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> anyhow::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or(anyhow!("{} is not a file path", path.display()))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum EntryKind {
+    Problem,
+    Input,
+    Submissions,
+}
+
+impl Display for EntryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EntryKind::Problem => "problem",
+            EntryKind::Input => "input",
+            EntryKind::Submissions => "submissions",
+        };
+        f.write_str(s)
+    }
+}
+
+impl EntryKind {
+    pub const ALL: [EntryKind; 3] = [EntryKind::Problem, EntryKind::Input, EntryKind::Submissions];
+
+    pub fn path(self, cache_dir: &Path, date: &EventDate) -> PathBuf {
+        let day_dir = cache_dir.join(format!("{}/day", date.year));
+        match self {
+            EntryKind::Problem => day_dir.join(format!("{}.html", date.day)),
+            EntryKind::Input => day_dir.join(format!("{}/input.txt", date.day)),
+            EntryKind::Submissions => day_dir.join(format!("{}/submissions.bin", date.day)),
+        }
+    }
+}
+
+/// A cached file for a single day.
+#[derive(Debug)]
+pub struct Entry {
+    pub date: EventDate,
+    pub kind: EntryKind,
+    pub path: PathBuf,
+}
+
+impl Entry {
+    /// Errors describing why the entry looks wrong, e.g. an error page or
+    /// HTML saved as an input.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let contents = fs::read(&self.path)?;
+        if contents.is_empty() {
+            return Err(anyhow!("empty file"));
+        }
+        match self.kind {
+            EntryKind::Problem => {
+                let html = String::from_utf8(contents)?;
+                if let Some(reason) = aoc_client::error_page_reason(&html, false) {
+                    return Err(anyhow!(reason));
+                }
+                if !html.contains("<article") {
+                    return Err(anyhow!("no puzzle description in the page"));
+                }
+            }
+            EntryKind::Input => {
+                let input = String::from_utf8(contents)?;
+                if let Some(reason) = aoc_client::error_page_reason(&input, true) {
+                    return Err(anyhow!(reason));
+                }
+            }
+            EntryKind::Submissions => {
+                bincode::decode_from_slice::<SubmissionHistory, _>(
+                    &contents,
+                    bincode::config::standard(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every cached entry in `cache_dir`, ordered by date, optionally limited to a year.
+pub fn entries(cache_dir: &Path, year: Option<u16>) -> anyhow::Result<Vec<Entry>> {
+    if !cache_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut years = fs::read_dir(cache_dir)?
+        .filter_map(|dir| dir.ok()?.file_name().to_str()?.parse::<u16>().ok())
+        .filter(|&cached_year| year.is_none_or(|year| year == cached_year))
+        .collect::<Vec<_>>();
+    years.sort();
+
+    let mut entries = Vec::new();
+    for year in years {
+        for day in 1..=EventDate::days_in_year(year) {
+            let date = EventDate { day, year };
+            for kind in EntryKind::ALL {
+                let path = kind.path(cache_dir, &date);
+                if path.exists() {
+                    entries.push(Entry { date, kind, path });
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
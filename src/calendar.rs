@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+/// The stars earned per day, as shown on the `/{year}` calendar page.
+#[derive(Debug, Default)]
+pub struct Calendar {
+    stars: BTreeMap<u8, u8>,
+}
+
+impl Calendar {
+    /// Reads each day's link, labelled e.g. "Day 3, two stars", falling back to
+    /// the `calendar-complete`/`calendar-verycomplete` classes.
+    pub fn parse(html: &str) -> anyhow::Result<Self> {
+        let link_re = Regex::new(r#"<a\s[^>]*class="calendar-day(\d+)([^"]*)"[^>]*>"#)?;
+        let label_re = Regex::new(r#"aria-label="Day \d+(?:, (one|two) stars?)?""#)?;
+        let mut stars = BTreeMap::new();
+        for caps in link_re.captures_iter(html) {
+            let day = caps[1].parse()?;
+            let count = match label_re
+                .captures(&caps[0])
+                .map(|label| label.get(1).map(|count| count.as_str()))
+            {
+                Some(Some("two")) => 2,
+                Some(Some(_)) => 1,
+                Some(None) => 0,
+                None if caps[2].contains("calendar-verycomplete") => 2,
+                None if caps[2].contains("calendar-complete") => 1,
+                None => 0,
+            };
+            stars.insert(day, count);
+        }
+        Ok(Calendar { stars })
+    }
+
+    /// Stars earned on `day`, `None` if the day isn't on the calendar yet.
+    pub fn stars(&self, day: u8) -> Option<u8> {
+        self.stars.get(&day).copied()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.stars.values().map(|&stars| u32::from(stars)).sum()
+    }
+}
@@ -0,0 +1,85 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::anyhow;
+
+const AOC_CONFIG: &str = "AOC_CONFIG";
+
+/// Settings read from `AOC_CONFIG`, or else `$XDG_CONFIG_HOME/aor/config`
+/// (`~/.config/aor/config`), one `key = value` per line:
+///
+/// ```text
+/// # Where problems, inputs and submission history are cached
+/// cache_dir = ~/.cache/aor
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    pub cache_dir: Option<PathBuf>,
+}
+
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// `$XDG_<KIND>_HOME`, falling back to `~/<fallback>`.
+pub fn xdg_dir(kind: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(format!("XDG_{kind}_HOME"))
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home_dir().map(|home| home.join(fallback)))
+}
+
+fn expand_home(path: &str) -> anyhow::Result<PathBuf> {
+    match path.strip_prefix("~/") {
+        Some(rest) => Ok(home_dir()
+            .ok_or(anyhow!("Can't expand ~ without HOME set"))?
+            .join(rest)),
+        None => Ok(PathBuf::from(path)),
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        env::var_os(AOC_CONFIG)
+            .map(PathBuf::from)
+            .or_else(|| xdg_dir("CONFIG", ".config").map(|dir| dir.join("aor/config")))
+    }
+
+    /// Loads the config file, a missing file is the same as an empty one.
+    pub fn load() -> anyhow::Result<Self> {
+        let Some(path) = Config::path() else {
+            return Ok(Config::default());
+        };
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let contents = fs::read_to_string(&path)?;
+        Config::parse(&contents).map_err(|err| anyhow!("Invalid config {}: {err}", path.display()))
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<Self> {
+        let mut config = Config::default();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(anyhow!("line {}: expected `key = value`", i + 1))?;
+            let value = value.trim().trim_matches('"');
+            match key.trim() {
+                "cache_dir" => {
+                    let cache_dir = expand_home(value)?;
+                    if !cache_dir.is_absolute() {
+                        return Err(anyhow!("line {}: cache_dir must be absolute", i + 1));
+                    }
+                    config.cache_dir = Some(cache_dir);
+                }
+                key => return Err(anyhow!("line {}: unknown setting `{key}`", i + 1)),
+            }
+        }
+        Ok(config)
+    }
+}
@@ -1,8 +1,11 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
 use anyhow::anyhow;
 use chrono::TimeZone;
 use chrono::{DateTime, Datelike, Local};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EventDate {
     pub day: u8,
     pub year: u16,
@@ -61,15 +64,74 @@ impl EventDate {
         }
         date
     }
-}
 
-impl TryInto<DateTime<chrono_tz::Tz>> for EventDate {
-    type Error = anyhow::Error;
+    /// The number of puzzles in a year's event.
+    pub fn days_in_year(year: u16) -> u8 {
+        if year < 2025 { 25 } else { 12 }
+    }
 
-    fn try_into(self) -> Result<DateTime<chrono_tz::Tz>, Self::Error> {
+    /// When the day's puzzle unlocks, midnight EST.
+    pub fn unlocks_at(&self) -> anyhow::Result<DateTime<chrono_tz::Tz>> {
+        if self.year < 2015 || self.day == 0 || self.day > Self::days_in_year(self.year) {
+            return Err(anyhow!(
+                "There is no puzzle for day {} of {}",
+                self.day,
+                self.year
+            ));
+        }
         chrono_tz::EST
             .with_ymd_and_hms(self.year.into(), 12, self.day.into(), 0, 0, 0)
             .earliest()
             .ok_or(anyhow!("Not a valid date 12/{}/{}", self.day, self.year))
     }
+
+    /// Errors if the day's puzzle hasn't unlocked yet, so nothing is requested
+    /// (and cached) before it's available.
+    pub fn ensure_unlocked(&self) -> anyhow::Result<()> {
+        let unlocks_at = self.unlocks_at()?;
+        let now = Local::now().with_timezone(&chrono_tz::EST);
+        if unlocks_at > now {
+            let remaining = unlocks_at - now;
+            return Err(anyhow!(
+                "Day {} of {} doesn't unlock until {unlocks_at} ({}h {}m from now)",
+                self.day,
+                self.year,
+                remaining.num_hours(),
+                remaining.num_minutes() % 60
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TryInto<DateTime<chrono_tz::Tz>> for EventDate {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<DateTime<chrono_tz::Tz>, Self::Error> {
+        self.unlocks_at()
+    }
+}
+
+/// A range of days given on the command line as `5`, `1..=25` or `1..26`.
+#[derive(Debug, Clone)]
+pub struct DayRange(pub RangeInclusive<u8>);
+
+impl FromStr for DayRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            start.parse()?..=end.parse()?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end: u8 = end.parse()?;
+            start.parse()?..=end.saturating_sub(1)
+        } else {
+            let day = s.parse()?;
+            day..=day
+        };
+        if range.is_empty() || *range.start() == 0 {
+            return Err(anyhow!("{s} is not a valid range of days"));
+        }
+        Ok(DayRange(range))
+    }
 }
@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use crate::{
+    problem::{CodeBlock, Problem},
+    solution::Part,
+};
+
+/// The confidence above which pairs besides the most likely one are generated
+/// without asking.
+const LIKELY_CONFIDENCE: f64 = 0.8;
+
+/// Words that usually appear in the sentence giving an example's answer.
+const ANSWER_WORDS: [&str; 9] = [
+    "example",
+    "total",
+    "result",
+    "answer",
+    "produce",
+    "give",
+    "would",
+    "sum",
+    "there are",
+];
+
+/// An example input from the puzzle description along with the answer it's
+/// expected to produce.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExamplePair {
+    pub input: CodeBlock,
+    pub answer: CodeBlock,
+    /// How likely the pair is to be right, from 0 to 1
+    pub confidence: f64,
+}
+
+impl Display for ExamplePair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let input = self
+            .input
+            .content
+            .lines()
+            .map(|line| format!("    {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        write!(
+            f,
+            "Input:\n{input}\nAnswer: {} (confidence {:.0}%)",
+            self.answer.content,
+            self.confidence * 100.0
+        )
+    }
+}
+
+fn same_sentence(a: &CodeBlock, b: &CodeBlock) -> bool {
+    a.part == b.part && a.context == b.context
+}
+
+/// Proposes (input, answer) pairs for `part`, most likely first. Answers are
+/// the emphasized values of the part's description, inputs are the `<pre>`
+/// blocks before them (including Part One's, which Part Two usually reuses) or
+/// inline code earlier in the same sentence, as in "`1122` produces `3`". In
+/// such sentences the last code is also taken as an answer even when it isn't
+/// emphasized.
+pub fn propose_pairs(problem: &Problem, part: Part) -> Vec<ExamplePair> {
+    let code_blocks = problem.code_blocks();
+    let is_candidate = |code: &CodeBlock| {
+        code.part == part && !code.block && !problem.answers.contains(&code.content)
+    };
+    let is_answer = |code: &CodeBlock| is_candidate(code) && code.emphasized;
+
+    let mut pairs: Vec<ExamplePair> = Vec::new();
+    for (answer_index, answer) in code_blocks.iter().enumerate() {
+        let ends_sentence = !code_blocks[answer_index + 1..]
+            .iter()
+            .any(|code| same_sentence(code, answer));
+        if !is_candidate(answer) || !(answer.emphasized || ends_sentence) {
+            continue;
+        }
+        let context = answer.context.to_lowercase();
+        let answer_words = ANSWER_WORDS.iter().any(|word| context.contains(word));
+
+        for (input_index, input) in code_blocks[..answer_index].iter().enumerate() {
+            let inline = same_sentence(input, answer) && !input.emphasized;
+            if !(inline || input.block && answer.emphasized) {
+                continue;
+            }
+            let between = &code_blocks[input_index + 1..answer_index];
+            let following = &code_blocks[answer_index + 1..];
+
+            let mut confidence = if answer.emphasized { 0.3 } else { 0.15 };
+            let (competing, later_answers) = if inline {
+                confidence += 0.25;
+                (
+                    between
+                        .iter()
+                        .filter(|code| same_sentence(code, answer) && !code.emphasized)
+                        .count(),
+                    following
+                        .iter()
+                        .any(|code| is_answer(code) && same_sentence(code, answer)),
+                )
+            } else {
+                confidence += 0.3;
+                (
+                    between.iter().filter(|code| code.block).count(),
+                    following
+                        .iter()
+                        .take_while(|code| !code.block)
+                        .any(is_answer),
+                )
+            };
+            confidence += 0.2 / (1 + competing) as f64;
+            if answer_words {
+                confidence += 0.1;
+            }
+            if !later_answers {
+                confidence += 0.1;
+            }
+            if input.part != part {
+                confidence -= 0.05;
+            }
+
+            let pair = ExamplePair {
+                input: input.clone(),
+                answer: answer.clone(),
+                confidence: confidence.clamp(0.0, 1.0),
+            };
+            match pairs.iter_mut().find(|existing| {
+                existing.input.content == pair.input.content
+                    && existing.answer.content == pair.answer.content
+            }) {
+                Some(existing) if existing.confidence < pair.confidence => *existing = pair,
+                Some(_) => {}
+                None => pairs.push(pair),
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    pairs
+}
+
+/// The pairs worth generating tests for: the most likely pair, plus any other
+/// likely pair with a different input, as when a puzzle gives several small
+/// examples with their own answers.
+pub fn distinct_pairs(pairs: &[ExamplePair]) -> Vec<&ExamplePair> {
+    let mut selected: Vec<&ExamplePair> = Vec::new();
+    for pair in pairs {
+        let distinct = selected
+            .iter()
+            .all(|selected| selected.input.content != pair.input.content);
+        if selected.is_empty() || distinct && pair.confidence >= LIKELY_CONFIDENCE {
+            selected.push(pair);
+        }
+    }
+    selected
+}
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use regex::{Captures, Regex};
+
+/// A minimal HTML tree, enough for the markup of AoC puzzle pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Text(String),
+    Element(Element),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub tag: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+const VOID_TAGS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The unescaped text of every descendant.
+    pub fn text(&self) -> String {
+        text(&self.children)
+    }
+
+    /// Every descendant element with the given tag, in document order.
+    pub fn find_all<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        find_all(&self.children, tag)
+    }
+}
+
+pub fn text(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element(element) => element.text(),
+        })
+        .collect()
+}
+
+pub fn find_all<'a>(nodes: &'a [Node], tag: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+        .flat_map(
+            move |element| -> Box<dyn Iterator<Item = &'a Element> + 'a> {
+                let descendants = element.find_all(tag);
+                if element.tag == tag {
+                    Box::new(std::iter::once(element).chain(descendants))
+                } else {
+                    Box::new(descendants)
+                }
+            },
+        )
+}
+
+/// Named entities keyed with their `&`, plus a semicolon except for the legacy
+/// ones like `&amp` that browsers also accept without one.
+static NAMED_ENTITIES: LazyLock<HashMap<&str, &str>> = LazyLock::new(|| {
+    entities::ENTITIES
+        .iter()
+        .map(|entity| (entity.entity, entity.characters))
+        .collect()
+});
+
+/// What browsers decode `&#128;` to `&#159;` as, since pages meant Windows-1252
+/// for those rather than the C1 control characters.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+fn numeric_entity(digits: &str, radix: u32) -> char {
+    match u32::from_str_radix(digits, radix) {
+        Ok(code @ 0x80..=0x9f) => WINDOWS_1252[(code - 0x80) as usize],
+        Ok(code) if code != 0 => char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER),
+        _ => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// The text for the named entity `&{name}`, where `name` includes the
+/// semicolon if there is one. Without an exact match the longest legacy entity
+/// at its start is decoded, so `&copy2024` is `©2024` as in a browser.
+fn named_entity(name: &str) -> Option<String> {
+    if let Some(characters) = NAMED_ENTITIES.get(format!("&{name}").as_str()) {
+        return Some(characters.to_string());
+    }
+    (1..name.len()).rev().find_map(|len| {
+        let (prefix, rest) = name.split_at(len);
+        NAMED_ENTITIES
+            .get(format!("&{prefix}").as_str())
+            .map(|characters| format!("{characters}{rest}"))
+    })
+}
+
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(?:#(?:[xX]([0-9a-fA-F]+)|([0-9]+));?|([A-Za-z][A-Za-z0-9]*;?))").unwrap()
+});
+
+static ATTRIBUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([\w-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#).unwrap()
+});
+
+static TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<!\w[^>]*>|<(/)?([a-zA-Z][a-zA-Z0-9]*)([^>]*?)/?>|([^<]+|<)")
+        .unwrap()
+});
+
+/// Decodes character references the way browsers do: every named entity from
+/// the HTML spec, decimal and hex references, and the legacy entities that
+/// may omit their semicolon. Anything else is left as written.
+pub fn unescape(input: &str) -> String {
+    ENTITY_RE
+        .replace_all(input, |captures: &Captures| {
+            if let Some(hex) = captures.get(1) {
+                numeric_entity(hex.as_str(), 16).to_string()
+            } else if let Some(decimal) = captures.get(2) {
+                numeric_entity(decimal.as_str(), 10).to_string()
+            } else {
+                named_entity(&captures[3]).unwrap_or_else(|| captures[0].to_string())
+            }
+        })
+        .into_owned()
+}
+
+fn parse_attributes(attributes: &str) -> Vec<(String, String)> {
+    ATTRIBUTE_RE
+        .captures_iter(attributes)
+        .map(|caps| {
+            let value = caps
+                .get(2)
+                .or(caps.get(3))
+                .or(caps.get(4))
+                .map_or(String::new(), |value| unescape(value.as_str()));
+            (caps[1].to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+/// Parses an HTML fragment, dropping comments. Unclosed elements are closed
+/// at the end of their parent and stray closing tags are ignored.
+pub fn parse(html: &str) -> Vec<Node> {
+    let mut stack = vec![Element {
+        tag: String::new(),
+        attributes: Vec::new(),
+        children: Vec::new(),
+    }];
+    for caps in TOKEN_RE.captures_iter(html) {
+        if let Some(text) = caps.get(4) {
+            let text = unescape(text.as_str());
+            let children = &mut stack.last_mut().unwrap().children;
+            match children.last_mut() {
+                Some(Node::Text(previous)) => previous.push_str(&text),
+                _ => children.push(Node::Text(text)),
+            }
+            continue;
+        }
+        let Some(tag) = caps.get(2) else {
+            continue;
+        };
+        let tag = tag.as_str().to_ascii_lowercase();
+        if caps.get(1).is_some() {
+            let Some(open) = stack.iter().rposition(|element| element.tag == tag) else {
+                continue;
+            };
+            while stack.len() > open {
+                let element = stack.pop().unwrap();
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .children
+                    .push(Node::Element(element));
+            }
+            continue;
+        }
+        let element = Element {
+            attributes: parse_attributes(&caps[3]),
+            tag,
+            children: Vec::new(),
+        };
+        if VOID_TAGS.contains(&element.tag.as_str()) || caps[0].ends_with("/>") {
+            stack
+                .last_mut()
+                .unwrap()
+                .children
+                .push(Node::Element(element));
+        } else {
+            stack.push(element);
+        }
+    }
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        stack
+            .last_mut()
+            .unwrap()
+            .children
+            .push(Node::Element(element));
+    }
+    stack.pop().unwrap().children
+}
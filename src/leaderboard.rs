@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::solution::Part;
+
+/// A private leaderboard as returned by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// Stars earned, keyed by day then part
+    pub completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Members in the order AoC ranks them, highest local score first and
+    /// earliest last star breaking ties.
+    pub fn ranked_members(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|member| (std::cmp::Reverse(member.local_score), member.last_star_ts));
+        members
+    }
+
+    /// The latest day any member has earned a star on.
+    pub fn last_day(&self) -> Option<u8> {
+        self.members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys())
+            .copied()
+            .max()
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star_time(&self, day: u8, part: Part) -> Option<DateTime<Utc>> {
+        let star = self.completion_day_level.get(&day)?.get(&part.to_int())?;
+        DateTime::from_timestamp(star.get_star_ts, 0)
+    }
+}
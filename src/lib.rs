@@ -1,10 +1,27 @@
 #![feature(portable_simd)]
 pub mod aoc_client;
+pub mod bench;
+pub mod bench_history;
+pub mod cache;
+pub mod calendar;
+pub mod config;
 pub mod event_date;
+pub mod example;
 pub mod grid;
+pub mod html;
+pub mod leaderboard;
+#[cfg(feature = "mock")]
+pub mod mock_server;
 pub mod parse;
 pub mod problem;
+pub mod profile;
+pub mod protocol;
+pub mod registry;
+pub mod render;
 pub mod solution;
 pub mod subcommands;
+pub mod submission;
+pub mod table;
+pub mod test_file;
 pub mod timing;
 pub mod util;
@@ -1,10 +1,25 @@
+// Solution modules can't enable features themselves once linked in
+#![cfg_attr(feature = "registry", feature(portable_simd))]
 use anyhow::Result;
 use aor::{
+    bench::{self, Budget},
+    bench_history::Baseline,
+    cache::EntryKind,
+    event_date::DayRange,
+    profile::Profile,
     solution::{self, Part},
-    subcommands,
+    subcommands::{self, ExampleSelection},
 };
 use clap::Parser;
-use std::path::PathBuf;
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+/// Solutions register themselves with `aor::registry`, so their modules only
+/// need to be linked in. Their own `main`s are unused here.
+#[cfg(feature = "registry")]
+#[allow(dead_code, unused_attributes)]
+mod solutions {
+    automod::dir!("src/solutions");
+}
 
 #[derive(Debug, clap::Subcommand)]
 enum Command {
@@ -25,6 +40,44 @@ enum Command {
         /// Build the solution in release mode
         #[arg(long)]
         release: bool,
+        /// Run against the input of every profile with a session token set
+        #[arg(long, conflicts_with = "input")]
+        all_profiles: bool,
+        /// Run every solution for the year against its cached input and summarize the results
+        #[arg(short, long, conflicts_with_all = ["day", "input", "all_profiles"])]
+        all: bool,
+    },
+    /// Benchmark the solution for a given day, built in release mode
+    Bench {
+        /// The day of the solution's problem (defaults to the day of the current date in EST)
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// The year of the solution's problem (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// The part(s) of the solution to benchmark, defaults to all parts
+        #[arg(short, long, default_values_t=[Part::One, Part::Two])]
+        parts: Vec<solution::Part>,
+        /// The number of iterations to time, after a tenth as many to warm up
+        #[arg(short, long, conflicts_with = "time")]
+        iterations: Option<u32>,
+        /// How long to benchmark each part for, e.g. `5s` or `500ms` (defaults to 3s)
+        #[arg(short, long, value_parser = bench::parse_duration)]
+        time: Option<Duration>,
+        /// Compare with the previous run of each part
+        #[arg(short, long)]
+        compare: bool,
+        /// Compare with the latest run at this commit instead
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Exit with an error when a part is significantly slower than the
+        /// baseline by more than this many percent (compares with the previous
+        /// run unless --baseline is given)
+        #[arg(long, value_name = "PERCENT")]
+        fail_above: Option<f64>,
+        /// Don't record the results in bench_history.bin
+        #[arg(long)]
+        no_save: bool,
     },
     /// Initialize a solution for a given day
     Init {
@@ -40,8 +93,42 @@ enum Command {
         /// Fetch input only, refetches if input is cached
         #[arg(short, long)]
         fetch_input_only: bool,
+        /// Also write the problem statement as Markdown next to the solution, `submit` keeps it
+        /// up to date once Part Two unlocks
+        #[arg(short, long, conflicts_with = "fetch_input_only")]
+        readme: bool,
+    },
+    /// Download every unlocked problem and input for a year into the cache
+    Fetch {
+        /// The year to fetch (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// The days to fetch, e.g. `5`, `1..=12` or `1..13` (defaults to every day)
+        #[arg(short, long)]
+        days: Option<DayRange>,
+        /// The minimum number of seconds to wait between requests
+        #[arg(long, default_value = "1", value_parser = subcommands::parse_delay)]
+        delay: Duration,
+    },
+    /// Show the puzzle description in the terminal
+    Show {
+        /// The day of the problem to show (defaults to today or the first day of December)
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// The year of the problem to show (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Only show this part (defaults to all unlocked parts)
+        #[arg(short, long)]
+        part: Option<Part>,
+        /// Print directly instead of through $PAGER (`less -R` by default)
+        #[arg(long)]
+        no_pager: bool,
     },
     /// Submit the answer for a given day
+    ///
+    /// Exits with 0 if correct, 2 if incorrect, 3 if rate limited, 4 if the part was already
+    /// completed and 5 if the part isn't the one currently being solved
     Submit {
         /// The day of the problem to submit a solution for (defaults to today OR the first day of December)
         #[arg(short, long)]
@@ -52,6 +139,40 @@ enum Command {
         /// The specific answer to submit (defaults to the output of the day's solution)
         #[arg(short, long)]
         answer: Option<String>,
+        /// Submit even if the answer is already known to be wrong
+        #[arg(long)]
+        force: bool,
+        /// Wait out any submission cooldown and resubmit instead of giving up
+        #[arg(short, long)]
+        wait: bool,
+    },
+    /// Manage the session token used to talk to Advent of Code
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommand,
+    },
+    /// Inspect and manage the cache of problems, inputs and submission history
+    ///
+    /// The cache lives in AOC_CACHE_DIR if set, otherwise in the `cache_dir` set in the config file
+    /// (AOC_CONFIG or ~/.config/aor/config), otherwise in ~/.cache/aor
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Show the stars earned each day next to the solutions and tests in this repository
+    Status {
+        /// The year to show (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+    /// Show a private leaderboard, refetched at most once every 15 minutes
+    Leaderboard {
+        /// The id of the private leaderboard (the number at the end of its URL)
+        #[arg(short, long)]
+        id: u64,
+        /// The year of the leaderboard (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
     },
     /// Generate tests using the example input and output from the problem description
     #[clap(name = "testgen")]
@@ -65,34 +186,168 @@ enum Command {
         /// The part(s) of the solution to generate tests for (defaults to all unlocked parts)
         #[arg(short, long)]
         parts: Vec<solution::Part>,
+        /// Use the most likely example input and answer without prompting
+        #[arg(long, conflicts_with = "input_index")]
+        auto: bool,
+        /// Use the example input at this position in the part's ranked candidates (as listed
+        /// by --dry-run) instead of prompting, for the single part given with -p
+        #[arg(long, requires = "answer_index")]
+        input_index: Option<usize>,
+        /// Use the example answer at this position in the part's ranked candidates (as listed
+        /// by --dry-run) instead of prompting
+        #[arg(long, requires = "input_index")]
+        answer_index: Option<usize>,
+        /// Print the numbered example candidates and the tests instead of adding them to the
+        /// solution
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum AuthCommand {
+    /// Check that the session token is set and valid, and show who it belongs to
+    Check,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum CacheCommand {
+    /// List cached entries by date with their sizes and ages
+    List {
+        /// Only list entries for this year
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+    /// Show the cached entries and submission history for a day
+    Show {
+        /// The day to show (defaults to today or the first day of December)
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// The year to show (defaults to the current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+    /// Delete cached entries
+    Purge {
+        /// The year to delete entries for
+        #[arg(short, long)]
+        year: u16,
+        /// Only delete entries for this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// The kind(s) of entry to delete (defaults to all kinds)
+        #[arg(short, long)]
+        kinds: Vec<EntryKind>,
     },
+    /// Check cached entries for error pages, login pages and HTML saved as inputs
+    Verify,
 }
 
 #[derive(Debug, clap::Parser)]
 struct Args {
     #[command(subcommand)]
     command: Command,
+    /// The account profile to use, its session token is read from AOC_AUTH_TOKEN_<PROFILE>
+    #[arg(long, global = true)]
+    profile: Option<String>,
 }
 
-fn main() -> Result<()> {
-    let command = Args::parse().command;
+fn main() -> Result<ExitCode> {
+    let Args { command, profile } = Args::parse();
+    Profile::select(Profile::named(profile)?)?;
     match command {
+        Command::Run {
+            year,
+            parts,
+            release: release_build,
+            all: true,
+            ..
+        } => return subcommands::run_all(year, &parts, release_build),
         Command::Run {
             day,
             year,
             parts,
             input,
             release: release_build,
-        } => subcommands::run(day, year, &parts, input, release_build),
+            all_profiles,
+            all: false,
+        } => return subcommands::run(day, year, &parts, input, release_build, all_profiles),
+        Command::Bench {
+            day,
+            year,
+            parts,
+            iterations,
+            time,
+            compare,
+            baseline,
+            fail_above,
+            no_save,
+        } => {
+            let budget = match (iterations, time) {
+                (Some(iterations), _) => Budget::Iterations(iterations),
+                (_, Some(time)) => Budget::Time(time),
+                _ => Budget::default(),
+            };
+            let baseline = match baseline {
+                Some(commit) => Some(Baseline::Commit(commit)),
+                None if compare => Some(Baseline::Previous),
+                None => None,
+            };
+            return subcommands::bench(day, year, &parts, budget, baseline, fail_above, !no_save);
+        }
         Command::Init {
             day,
             year,
             countdown,
             fetch_input_only,
-        } => subcommands::init(day, year, countdown, fetch_input_only),
-        Command::Submit { day, year, answer } => subcommands::submit(day, year, answer),
-        Command::GenerateTests { day, year, parts } => {
-            subcommands::generate_tests(day, year, &parts)
+            readme,
+        } => subcommands::init(day, year, countdown, fetch_input_only, readme)?,
+        Command::Fetch { year, days, delay } => subcommands::fetch(year, days, delay)?,
+        Command::Show {
+            day,
+            year,
+            part,
+            no_pager,
+        } => subcommands::show(day, year, part, no_pager)?,
+        Command::Submit {
+            day,
+            year,
+            answer,
+            force,
+            wait,
+        } => {
+            let verdict = subcommands::submit(day, year, answer, force, wait)?;
+            return Ok(verdict.exit_code());
+        }
+        Command::Auth {
+            command: AuthCommand::Check,
+        } => subcommands::check_auth()?,
+        Command::Cache { command } => match command {
+            CacheCommand::List { year } => subcommands::cache_list(year)?,
+            CacheCommand::Show { day, year } => subcommands::cache_show(day, year)?,
+            CacheCommand::Purge { year, day, kinds } => {
+                subcommands::cache_purge(year, day, &kinds)?
+            }
+            CacheCommand::Verify => subcommands::cache_verify()?,
+        },
+        Command::Status { year } => subcommands::status(year)?,
+        Command::Leaderboard { id, year } => subcommands::leaderboard(id, year)?,
+        Command::GenerateTests {
+            day,
+            year,
+            parts,
+            auto,
+            input_index,
+            answer_index,
+            dry_run,
+        } => {
+            let selection = match (input_index, answer_index) {
+                (Some(input), Some(answer)) => ExampleSelection::Indices { input, answer },
+                _ if auto => ExampleSelection::Auto,
+                _ => ExampleSelection::Prompt,
+            };
+            subcommands::generate_tests(day, year, &parts, selection, dry_run)?
         }
     }
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,35 @@
+use std::{net::TcpListener, path::PathBuf, time::Duration};
+
+use aor::mock_server::MockAoc;
+use clap::Parser;
+
+/// Serve a fake Advent of Code site from a fixtures directory, point `aor` at it with `AOC_URL`
+#[derive(Debug, clap::Parser)]
+struct Args {
+    /// Directory of problems, inputs and answers laid out like the cache
+    fixtures_dir: PathBuf,
+    /// The port to listen on
+    #[arg(short, long, default_value_t = 8080)]
+    port: u16,
+    /// How long a wrong answer locks out further answers for a day
+    #[arg(long, default_value_t = 60)]
+    cooldown_secs: u64,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Args {
+        fixtures_dir,
+        port,
+        cooldown_secs,
+    } = Args::parse();
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!(
+        "Serving {} at http://{}",
+        fixtures_dir.display(),
+        listener.local_addr()?
+    );
+    MockAoc::new(fixtures_dir)
+        .cooldown(Duration::from_secs(cooldown_secs))
+        .serve(listener);
+    Ok(())
+}
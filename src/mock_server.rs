@@ -0,0 +1,365 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A stand-in for adventofcode.com that serves problems, inputs and answer
+/// responses from a fixtures directory laid out like the cache:
+///
+/// ```text
+/// {year}.html                       calendar
+/// {year}/day/{day}.html             problem page
+/// {year}/day/{day}/input.txt        puzzle input
+/// {year}/day/{day}/answer/{part}.txt  correct answer for a part
+/// {year}/day/{day}/part2.html       added to the problem page once Part One is solved
+/// {year}/leaderboard/private/view/{id}.json  private leaderboard
+/// ```
+///
+/// Like the real site, a wrong answer locks out further answers, for every
+/// day, until `cooldown` has passed. Requests without a session, or with the session
+/// `expired`, are treated as logged out.
+pub struct MockAoc {
+    fixtures_dir: PathBuf,
+    cooldown: Duration,
+    locked_until: Mutex<Option<Instant>>,
+    /// Days whose Part One has been answered correctly
+    solved: Mutex<HashSet<String>>,
+}
+
+impl MockAoc {
+    pub fn new(fixtures_dir: impl Into<PathBuf>) -> Self {
+        MockAoc {
+            fixtures_dir: fixtures_dir.into(),
+            cooldown: Duration::from_secs(60),
+            locked_until: Mutex::new(None),
+            solved: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn serve(&self, listener: TcpListener) {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let response = match read_request(&mut stream) {
+                Ok(request) => self.route(&request),
+                Err(err) => Response::bad_request(&err.to_string()),
+            };
+            if let Err(err) = write_response(&mut stream, &response) {
+                eprintln!("mock_aoc: failed to write response: {err}");
+            }
+        }
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        let resource = request.path.trim_start_matches('/');
+        let segments = resource.split('/').collect::<Vec<_>>();
+        let logged_in = request
+            .session
+            .as_deref()
+            .is_some_and(|session| session != "expired");
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["settings"]) => {
+                if !logged_in {
+                    return Response::redirect("/auth/login");
+                }
+                Response::ok(
+                    "<header><div class=\"user\">mock user <span class=\"star-count\">0*</span></div></header>"
+                        .to_string(),
+                )
+            }
+            ("GET", [_year]) => read_fixture(&self.fixtures_dir.join(format!("{resource}.html"))),
+            ("GET", [year, "day", day]) => {
+                let mut response =
+                    read_fixture(&self.fixtures_dir.join(format!("{resource}.html")));
+                let part_two = self.fixtures_dir.join(format!("{resource}/part2.html"));
+                if self
+                    .solved
+                    .lock()
+                    .unwrap()
+                    .contains(&format!("{year}/{day}"))
+                    && let Ok(part_two) = fs::read_to_string(part_two)
+                {
+                    response.body =
+                        response
+                            .body
+                            .replacen("</article>", &format!("</article>\n{part_two}"), 1);
+                }
+                if logged_in {
+                    return response;
+                }
+                Response {
+                    body: response
+                        .body
+                        .replace("<main>", "<a href=\"/auth/login\">[Log In]</a>\n<main>"),
+                    ..response
+                }
+            }
+            ("GET", [_year, "day", _day, "input"]) => {
+                if !logged_in {
+                    return Response::bad_request(
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                    );
+                }
+                read_fixture(&self.fixtures_dir.join(format!("{resource}.txt")))
+            }
+            ("GET", [_year, "leaderboard", "private", "view", _id]) => {
+                read_fixture(&self.fixtures_dir.join(resource))
+            }
+            ("POST", [year, "day", day, "answer"]) => {
+                let (level, answer) = parse_answer_form(&request.body);
+                Response::ok(answer_page(
+                    &self.answer_message(year, day, &level, &answer),
+                ))
+            }
+            _ => Response::not_found(),
+        }
+    }
+
+    fn answer_message(&self, year: &str, day: &str, level: &str, answer: &str) -> String {
+        let mut locked_until = self.locked_until.lock().unwrap();
+        let key = format!("{year}/{day}");
+        let now = Instant::now();
+        if let Some(until) = *locked_until
+            && until > now
+        {
+            let remaining = (until - now).as_secs_f64().ceil() as u64;
+            let wait = match remaining / 60 {
+                0 => format!("{remaining}s"),
+                minutes => format!("{minutes}m {}s", remaining % 60),
+            };
+            return format!(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {wait} left to wait. [<a href=\"/{year}/day/{day}\">Return to Day {day}</a>]"
+            );
+        }
+
+        let answer_path = self
+            .fixtures_dir
+            .join(format!("{year}/day/{day}/answer"))
+            .join(format!("{level}.txt"));
+        let Ok(expected) = fs::read_to_string(answer_path) else {
+            return format!(
+                "You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/{year}/day/{day}\">Return to Day {day}</a>]"
+            );
+        };
+        let expected = expected.trim();
+        if answer == expected {
+            if level == "1" {
+                self.solved.lock().unwrap().insert(key);
+            }
+            return format!(
+                "That's the right answer!  You are one gold star closer to finishing. [<a href=\"/{year}/day/{day}\">Continue to Part Two</a>]"
+            );
+        }
+
+        *locked_until = Some(now + self.cooldown);
+        let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+            (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+            _ => "",
+        };
+        // AoC words its wait in minutes, seconds only come up with short test cooldowns
+        let wait = match self.cooldown.as_secs() {
+            60 => "one minute".to_string(),
+            seconds if seconds % 60 == 0 && seconds > 0 => format!("{} minutes", seconds / 60),
+            seconds => format!("{seconds} seconds"),
+        };
+        format!(
+            "That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.  Please wait {wait} before trying again. [<a href=\"/{year}/day/{day}\">Return to Day {day}</a>]"
+        )
+    }
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+}
+
+impl MockServer {
+    /// Serves `mock` on a random local port from a background thread.
+    pub fn start(mock: MockAoc) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let mock = Arc::new(mock);
+        thread::spawn(move || mock.serve(listener));
+        Ok(MockServer { addr })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    session: Option<String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    location: Option<String>,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Response {
+            status: 200,
+            reason: "OK",
+            location: None,
+            body,
+        }
+    }
+
+    fn not_found() -> Self {
+        Response {
+            status: 404,
+            reason: "Not Found",
+            location: None,
+            body: "404 Not Found".to_string(),
+        }
+    }
+
+    fn redirect(location: &str) -> Self {
+        Response {
+            status: 302,
+            reason: "Found",
+            location: Some(location.to_string()),
+            body: String::new(),
+        }
+    }
+
+    fn bad_request(body: &str) -> Self {
+        Response {
+            status: 400,
+            reason: "Bad Request",
+            location: None,
+            body: body.to_string(),
+        }
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut request_line = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err(io::Error::other("malformed request line"));
+    };
+
+    let mut content_length = 0;
+    let mut session = None;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().map_err(io::Error::other)?;
+        } else if name.eq_ignore_ascii_case("cookie") {
+            session = value
+                .split(';')
+                .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                .next()
+                .map(str::to_string);
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        session,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\n",
+        response.status, response.reason
+    )?;
+    if let Some(location) = &response.location {
+        write!(stream, "Location: {location}\r\n")?;
+    }
+    write!(
+        stream,
+        "Content-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn read_fixture(path: &Path) -> Response {
+    match fs::read_to_string(path) {
+        Ok(contents) => Response::ok(contents),
+        Err(_) => Response::not_found(),
+    }
+}
+
+fn url_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn parse_answer_form(body: &str) -> (String, String) {
+    let mut level = String::new();
+    let mut answer = String::new();
+    for pair in body.split('&') {
+        match pair.split_once('=') {
+            Some(("level", value)) => level = url_decode(value),
+            Some(("answer", value)) => answer = url_decode(value),
+            _ => {}
+        }
+    }
+    (level, answer)
+}
+
+fn answer_page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en-us\">\n<body>\n<main>\n<article><p>{message}</p></article>\n</main>\n</body>\n</html>"
+    )
+}
@@ -1,13 +1,24 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range, sync::LazyLock};
 
 use regex::Regex;
 
-use crate::solution::Part;
+use crate::{
+    html::{self, Element, Node},
+    solution::Part,
+};
 
+/// A code snippet from the puzzle description, either inline `<code>` or a
+/// `<pre><code>` block.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CodeBlock {
     pub content: String,
     pub emphasized: bool,
+    /// Whether the code is a `<pre>` block rather than inline
+    pub block: bool,
+    /// The sentence the code appears in, or for a `<pre>` block the sentence
+    /// introducing it.
+    pub context: String,
+    pub part: Part,
 }
 
 impl CodeBlock {
@@ -22,60 +33,280 @@ impl Display for CodeBlock {
     }
 }
 
-fn html_unescape(input: &str) -> String {
-    input
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&amp;", "&")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'")
-}
-
-fn clean_html(input: &str) -> String {
-    let re = Regex::new(r"(?s)<[^>]*>").unwrap();
-    html_unescape(&re.replace_all(input, "").trim_end())
-}
-
-pub fn parse_code_blocks(problem_html: &str) -> Vec<CodeBlock> {
-    let code_block_re = Regex::new(r#"<code>(?s:(.*?))</code>"#).unwrap();
-    let emphasized_re = Regex::new(r"^<em>(?s:(.*?))</em>$").unwrap();
-    let mut code_blocks = Vec::new();
-    for cap in code_block_re.captures_iter(problem_html) {
-        let content = cap.get(1).unwrap().as_str();
-        if let Some(emphasized_cap) = emphasized_re.captures(&content) {
-            let emphasized_content = emphasized_cap.get(1).unwrap().as_str();
-            code_blocks.push(CodeBlock {
-                content: clean_html(&emphasized_content),
-                emphasized: true,
-            });
-        } else {
-            code_blocks.push(CodeBlock {
-                content: clean_html(content),
-                emphasized: false,
-            });
+/// Inline content of a paragraph or list item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    /// `<code>`, emphasized when wrapped in or wrapping `<em>`
+    Code {
+        text: String,
+        emphasized: bool,
+    },
+    Emphasis(String),
+    Link {
+        text: String,
+        href: String,
+    },
+}
+
+impl Inline {
+    pub fn text(&self) -> &str {
+        match self {
+            Inline::Text(text) | Inline::Emphasis(text) => text,
+            Inline::Code { text, .. } | Inline::Link { text, .. } => text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Paragraph(Vec<Inline>),
+    List(Vec<Vec<Inline>>),
+    /// A `<pre><code>` block
+    Code(String),
+}
+
+/// The description of one part of the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Article {
+    pub part: Part,
+    /// e.g. "Day 1: Not Quite Lisp" or "Part Two"
+    pub heading: String,
+    pub blocks: Vec<Block>,
+}
+
+impl Article {
+    /// Every `<em>` value in the article, including emphasized code.
+    pub fn emphasized(&self) -> Vec<&str> {
+        self.blocks
+            .iter()
+            .flat_map(|block| match block {
+                Block::Paragraph(inlines) => inlines.iter().collect::<Vec<_>>(),
+                Block::List(items) => items.iter().flatten().collect(),
+                Block::Code(_) => Vec::new(),
+            })
+            .filter_map(|inline| match inline {
+                Inline::Emphasis(text)
+                | Inline::Code {
+                    text,
+                    emphasized: true,
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Every code snippet in the article along with the sentence around it.
+    pub fn code_blocks(&self) -> Vec<CodeBlock> {
+        let mut code_blocks = Vec::new();
+        let mut previous_sentence = String::new();
+        for block in &self.blocks {
+            let items = match block {
+                Block::Paragraph(inlines) => std::slice::from_ref(inlines),
+                Block::List(items) => items.as_slice(),
+                Block::Code(content) => {
+                    code_blocks.push(CodeBlock {
+                        content: content.clone(),
+                        emphasized: false,
+                        block: true,
+                        context: previous_sentence.clone(),
+                        part: self.part,
+                    });
+                    continue;
+                }
+            };
+            for inlines in items {
+                let mut text = String::new();
+                let mut codes = Vec::new();
+                for inline in inlines {
+                    let start = text.len();
+                    text.push_str(inline.text());
+                    if let Inline::Code {
+                        text: content,
+                        emphasized,
+                    } = inline
+                    {
+                        codes.push((content, *emphasized, start..text.len()));
+                    }
+                }
+                for (content, emphasized, range) in codes {
+                    code_blocks.push(CodeBlock {
+                        content: content.trim_end().to_string(),
+                        emphasized,
+                        block: false,
+                        context: sentence_around(&text, range),
+                        part: self.part,
+                    });
+                }
+                previous_sentence = sentence_around(&text, text.len()..text.len());
+            }
+        }
+        code_blocks
+    }
+}
+
+/// A puzzle page parsed once, so commands don't have to re-scan the HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub title: String,
+    /// One article per unlocked part
+    pub articles: Vec<Article>,
+    /// Answers already accepted, in part order
+    pub answers: Vec<String>,
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+static SENTENCE_END_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[.!?]\s+").unwrap());
+
+/// The sentence of `text` containing `range`.
+fn sentence_around(text: &str, range: Range<usize>) -> String {
+    let mut start = 0;
+    let mut end = text.len();
+    for boundary in SENTENCE_END_RE.find_iter(text) {
+        if boundary.end() <= range.start {
+            start = boundary.end();
+        } else if boundary.start() >= range.end {
+            end = boundary.start() + 1;
+            break;
+        }
+    }
+    collapse_whitespace(&text[start..end])
+}
+
+fn push_text(inlines: &mut Vec<Inline>, text: &str, emphasized: bool) {
+    match (inlines.last_mut(), emphasized) {
+        (Some(Inline::Text(previous)), false) | (Some(Inline::Emphasis(previous)), true) => {
+            previous.push_str(text)
         }
+        (_, false) => inlines.push(Inline::Text(text.to_string())),
+        (_, true) => inlines.push(Inline::Emphasis(text.to_string())),
     }
-    code_blocks
 }
 
-pub fn previous_answers(problem_html: &str) -> Vec<String> {
-    let answer_re = Regex::new(r#"(?s:Your puzzle answer was\s*<code>(.*?)</code>)"#).unwrap();
-    let mut answers = Vec::new();
-    for cap in answer_re.captures_iter(problem_html) {
-        let answer = cap.get(1).unwrap().as_str().to_owned();
-        answers.push(answer);
+fn parse_inlines(nodes: &[Node], emphasized: bool, inlines: &mut Vec<Inline>) {
+    for node in nodes {
+        let element = match node {
+            Node::Text(text) => {
+                push_text(inlines, text, emphasized);
+                continue;
+            }
+            Node::Element(element) => element,
+        };
+        match element.tag.as_str() {
+            "code" => {
+                let wraps_em = matches!(
+                    element.children.as_slice(),
+                    [Node::Element(Element { tag, .. })] if tag == "em"
+                );
+                inlines.push(Inline::Code {
+                    text: element.text(),
+                    emphasized: emphasized || wraps_em,
+                });
+            }
+            "em" => parse_inlines(&element.children, true, inlines),
+            "a" => inlines.push(Inline::Link {
+                text: element.text(),
+                href: element.attribute("href").unwrap_or_default().to_string(),
+            }),
+            "br" => push_text(inlines, "\n", emphasized),
+            _ => parse_inlines(&element.children, emphasized, inlines),
+        }
     }
-    answers
 }
 
-pub fn part_two_unlocked(problem_html: &str) -> bool {
-    problem_html.contains("--- Part Two ---")
+fn inlines(element: &Element) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    parse_inlines(&element.children, false, &mut inlines);
+    inlines
+}
+
+fn parse_heading(heading: &str) -> String {
+    heading
+        .trim()
+        .trim_start_matches("---")
+        .trim_end_matches("---")
+        .trim()
+        .to_string()
 }
 
-pub fn unlocked_parts(problem_html: &str) -> Vec<Part> {
-    if part_two_unlocked(problem_html) {
-        vec![Part::One, Part::Two]
-    } else {
-        vec![Part::One]
+fn parse_article(element: &Element, part: Part) -> Article {
+    let mut heading = String::new();
+    let mut blocks = Vec::new();
+    for node in &element.children {
+        let Node::Element(child) = node else {
+            continue;
+        };
+        match child.tag.as_str() {
+            "h2" => heading = parse_heading(&child.text()),
+            "pre" => blocks.push(Block::Code(child.text().trim_end().to_string())),
+            "ul" | "ol" => blocks.push(Block::List(
+                child
+                    .children
+                    .iter()
+                    .filter_map(|node| match node {
+                        Node::Element(item) if item.tag == "li" => Some(inlines(item)),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            _ => blocks.push(Block::Paragraph(inlines(child))),
+        }
+    }
+    Article {
+        part,
+        heading,
+        blocks,
+    }
+}
+
+impl Problem {
+    pub fn parse(problem_html: &str) -> Self {
+        let nodes = html::parse(problem_html);
+        let articles = html::find_all(&nodes, "article")
+            .zip([Part::One, Part::Two])
+            .map(|(article, part)| parse_article(article, part))
+            .collect::<Vec<_>>();
+        let title = articles
+            .first()
+            .map(|article| {
+                article
+                    .heading
+                    .split_once(": ")
+                    .map_or(article.heading.as_str(), |(_, title)| title)
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let answers = html::find_all(&nodes, "p")
+            .filter(|p| p.text().trim_start().starts_with("Your puzzle answer was"))
+            .filter_map(|p| p.find_all("code").next().map(Element::text))
+            .collect();
+        Problem {
+            title,
+            articles,
+            answers,
+        }
+    }
+
+    pub fn article(&self, part: Part) -> Option<&Article> {
+        self.articles.iter().find(|article| article.part == part)
+    }
+
+    pub fn part_two_unlocked(&self) -> bool {
+        self.article(Part::Two).is_some()
+    }
+
+    pub fn unlocked_parts(&self) -> Vec<Part> {
+        self.articles.iter().map(|article| article.part).collect()
+    }
+
+    /// Every code snippet on the page, in document order.
+    pub fn code_blocks(&self) -> Vec<CodeBlock> {
+        self.articles
+            .iter()
+            .flat_map(Article::code_blocks)
+            .collect()
     }
 }
@@ -0,0 +1,96 @@
+use std::{env, fmt::Display, path::PathBuf, sync::OnceLock};
+
+use anyhow::anyhow;
+
+use crate::cache;
+
+const AOC_AUTH_TOKEN: &str = "AOC_AUTH_TOKEN";
+
+static SELECTED: OnceLock<Profile> = OnceLock::new();
+
+/// An AoC account. The default profile's session token is read from
+/// `AOC_AUTH_TOKEN`, a profile named `alt` reads `AOC_AUTH_TOKEN_ALT` and keeps
+/// its own cache under `profiles/alt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub name: Option<String>,
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name.as_deref().unwrap_or("default"))
+    }
+}
+
+impl Profile {
+    /// The profile called `name`, which may only contain ASCII letters, digits,
+    /// `_` and `-` since it names a directory in the cache.
+    pub fn named(name: Option<String>) -> anyhow::Result<Self> {
+        if let Some(name) = &name {
+            let valid = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid {
+                return Err(anyhow!(
+                    "Invalid profile name {name:?}, only letters, digits, _ and - are allowed"
+                ));
+            }
+        }
+        Ok(Profile {
+            name: name.map(|name| name.to_lowercase()),
+        })
+    }
+
+    /// Selects the profile used for the rest of the process, may only be called once.
+    pub fn select(profile: Profile) -> anyhow::Result<()> {
+        SELECTED
+            .set(profile)
+            .map_err(|profile| anyhow!("Profile already selected, can't switch to {profile}"))
+    }
+
+    pub fn selected() -> &'static Profile {
+        SELECTED.get_or_init(Profile::default)
+    }
+
+    /// Every profile with a session token set in the environment.
+    pub fn all() -> Vec<Profile> {
+        let prefix = format!("{AOC_AUTH_TOKEN}_");
+        let mut profiles = env::vars()
+            .filter_map(|(key, _)| key.strip_prefix(&prefix).map(str::to_string))
+            .filter_map(|name| Profile::named(Some(name)).ok())
+            .collect::<Vec<_>>();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        if env::var_os(AOC_AUTH_TOKEN).is_some() {
+            profiles.insert(0, Profile::default());
+        }
+        profiles
+    }
+
+    pub fn auth_token_var(&self) -> String {
+        match &self.name {
+            Some(name) => {
+                let suffix = name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() {
+                            c.to_ascii_uppercase()
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>();
+                format!("{AOC_AUTH_TOKEN}_{suffix}")
+            }
+            None => AOC_AUTH_TOKEN.to_string(),
+        }
+    }
+
+    pub fn cache_dir(&self) -> anyhow::Result<PathBuf> {
+        let cache_dir = cache::dir()?;
+        match &self.name {
+            Some(name) => Ok(cache_dir.join("profiles").join(name)),
+            None => Ok(cache_dir),
+        }
+    }
+}
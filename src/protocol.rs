@@ -0,0 +1,142 @@
+use std::{
+    any::Any,
+    env,
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+
+use crate::bench::Phase;
+
+/// The environment variable `aor` sets to the file a solution reports to.
+pub const REPORT_PATH_VAR: &str = "AOR_REPORT_PATH";
+
+/// A message from a solution binary to `aor`. Each is written as a
+/// little-endian `u32` length followed by that many bytes of bincode, so a
+/// report cut short by a crash is detected rather than misread.
+#[derive(Debug, Clone, PartialEq, bincode::Encode, bincode::Decode)]
+pub enum Frame {
+    /// A named measurement recorded while solving, e.g. a cache hit count
+    Metric {
+        name: String,
+        value: f64,
+    },
+    Answer {
+        answer: String,
+        duration: Duration,
+    },
+    /// The timings of a phase when benchmarking
+    Samples {
+        phase: Phase,
+        samples: Vec<Duration>,
+    },
+    Error {
+        message: String,
+    },
+    Panic {
+        message: String,
+    },
+}
+
+pub fn write_frame(writer: &mut impl Write, frame: &Frame) -> anyhow::Result<()> {
+    let payload = bincode::encode_to_vec(frame, bincode::config::standard())?;
+    writer.write_all(&u32::try_from(payload.len())?.to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads every frame up to the end of `reader`.
+pub fn read_frames(reader: &mut impl Read) -> anyhow::Result<Vec<Frame>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let mut frames = Vec::new();
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let (len, payload) = rest
+            .split_first_chunk::<4>()
+            .ok_or(anyhow!("Truncated frame length"))?;
+        let len = u32::from_le_bytes(*len) as usize;
+        if payload.len() < len {
+            return Err(anyhow!(
+                "Truncated frame, expected {len} bytes but only {} were written",
+                payload.len()
+            ));
+        }
+        let (frame, _) = bincode::decode_from_slice(&payload[..len], bincode::config::standard())?;
+        frames.push(frame);
+        rest = &payload[len..];
+    }
+    Ok(frames)
+}
+
+pub fn read_report(path: &Path) -> anyhow::Result<Vec<Frame>> {
+    match File::open(path) {
+        Ok(mut file) => read_frames(&mut file),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The report file when run by `aor`, `None` when run by hand.
+fn report_file() -> Option<&'static Mutex<File>> {
+    static REPORT: OnceLock<Option<Mutex<File>>> = OnceLock::new();
+    REPORT
+        .get_or_init(|| {
+            let path = env::var_os(REPORT_PATH_VAR)?;
+            File::create(path).ok().map(Mutex::new)
+        })
+        .as_ref()
+}
+
+/// Sends `frame` to `aor`, returning whether the solution is being run by it.
+pub fn send(frame: &Frame) -> anyhow::Result<bool> {
+    let Some(file) = report_file() else {
+        return Ok(false);
+    };
+    let mut file = file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    write_frame(&mut *file, frame)?;
+    Ok(true)
+}
+
+/// Records a measurement for `aor run` to show next to the answer. Run by
+/// hand, the solution prints it to stderr instead.
+pub fn metric(name: impl Into<String>, value: impl Into<f64>) {
+    let frame = Frame::Metric {
+        name: name.into(),
+        value: value.into(),
+    };
+    if !send(&frame).unwrap_or(false)
+        && let Frame::Metric { name, value } = frame
+    {
+        eprintln!("{name}: {value}");
+    }
+}
+
+/// The message a panic was raised with.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// Reports panics to `aor` before the default hook prints them, which still
+/// happens with `panic = "abort"`.
+pub fn report_panics() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info.payload());
+        let message = match info.location() {
+            Some(location) => format!("{message} at {location}"),
+            None => message,
+        };
+        let _ = send(&Frame::Panic { message });
+        default_hook(info);
+    }));
+}
@@ -0,0 +1,158 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+#[doc(hidden)]
+pub use inventory;
+
+use anyhow::anyhow;
+
+use crate::{
+    bench::{self, Budget, Measurement, ParseFn},
+    event_date::EventDate,
+    protocol,
+    solution::{self, ExecResult, Part, PartFn},
+    timing,
+};
+
+/// A solution registered with [`register_solution!`](crate::register_solution).
+/// Solutions are only linked into the `aor` binary when it's built with the
+/// `registry` feature, otherwise nothing is registered and each day runs as its
+/// own binary.
+///
+/// Linked solutions are compiled when `aor` itself is built, so `aor` has to be
+/// rebuilt to pick up edits to them or days added since.
+pub struct Solution {
+    /// The solution's source file, named after the day like `day_01_2025.rs`
+    pub file: &'static str,
+    pub part1: PartFn,
+    pub part2: PartFn,
+    /// The input parser, for `aor bench` to time on its own
+    pub parse: Option<ParseFn>,
+}
+
+inventory::collect!(Solution);
+
+/// Registers a solution's parts so the `aor` binary can run them in-process.
+/// The date is taken from the file name. An input parser taking the input
+/// `String` can be passed as well so `aor bench` times parsing separately.
+#[macro_export]
+macro_rules! register_solution {
+    ($part1:path, $part2:path) => {
+        $crate::registry::inventory::submit! {
+            $crate::registry::Solution {
+                file: file!(),
+                part1: $part1,
+                part2: $part2,
+                parse: None,
+            }
+        }
+    };
+    ($part1:path, $part2:path, $parse:expr) => {
+        $crate::registry::inventory::submit! {
+            $crate::registry::Solution {
+                file: file!(),
+                part1: $part1,
+                part2: $part2,
+                parse: Some($crate::parse_fn!($parse)),
+            }
+        }
+    };
+}
+
+/// Turns an input parser taking the input `String` into a
+/// [`ParseFn`](crate::bench::ParseFn), keeping what it returns from being
+/// optimized away.
+#[macro_export]
+macro_rules! parse_fn {
+    ($parse:expr) => {
+        (|input: String| {
+            ::std::hint::black_box(($parse)(input));
+        }) as $crate::bench::ParseFn
+    };
+}
+
+impl Solution {
+    pub fn date(&self) -> Option<EventDate> {
+        let stem = Path::new(self.file).file_stem()?.to_str()?;
+        solution::parse_bin_name(stem)
+    }
+
+    pub fn part(&self, part: Part) -> PartFn {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+
+    /// Runs `part` in this process, catching panics so one day can't take
+    /// down a run of several. That needs `aor` built to unwind: the release
+    /// profile aborts on panic, build with `--profile registry` instead.
+    pub fn run(&self, part: Part, input: String) -> ExecResult {
+        let solve = self.part(part);
+        let result = timing::it(|| panic::catch_unwind(AssertUnwindSafe(|| solve(input))));
+        match result.result {
+            Ok(Ok(answer)) => ExecResult::Complete {
+                answer,
+                duration: result.duration,
+                metrics: Vec::new(),
+            },
+            Ok(Err(err)) => ExecResult::Failed(format!("{err:?}")),
+            Err(payload) => ExecResult::Failed(format!(
+                "Panicked: {}",
+                protocol::panic_message(payload.as_ref())
+            )),
+        }
+    }
+
+    /// Benchmarks `part` in this process, catching panics like [`Solution::run`].
+    pub fn bench(
+        &self,
+        part: Part,
+        input: &str,
+        budget: Budget,
+    ) -> anyhow::Result<Vec<Measurement>> {
+        let solve = self.part(part);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            bench::bench_part(solve, self.parse, input, budget)
+        }))
+        .unwrap_or_else(|payload| {
+            Err(anyhow!(
+                "Panicked: {}",
+                protocol::panic_message(payload.as_ref())
+            ))
+        })
+    }
+}
+
+/// Every registered solution with its date, in date order.
+pub fn solutions() -> Vec<(EventDate, &'static Solution)> {
+    let mut solutions = inventory::iter::<Solution>
+        .into_iter()
+        .filter_map(|solution| Some((solution.date()?, solution)))
+        .collect::<Vec<_>>();
+    solutions.sort_by_key(|(date, _)| (date.year, date.day));
+    solutions
+}
+
+pub fn find(date: &EventDate) -> Option<&'static Solution> {
+    solutions()
+        .into_iter()
+        .find(|(registered, _)| registered == date)
+        .map(|(_, solution)| solution)
+}
+
+/// Like [`find`], but with the `registry` feature a missing solution is an
+/// error. It was added after `aor` was built, and `init` gave it no binary to
+/// fall back on.
+pub fn find_linked(date: &EventDate) -> anyhow::Result<Option<&'static Solution>> {
+    match find(date) {
+        None if cfg!(feature = "registry") => Err(anyhow!(
+            "Day {} of {} isn't linked into this build of aor, rebuild it with --features registry",
+            date.day,
+            date.year
+        )),
+        found => Ok(found),
+    }
+}
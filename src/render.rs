@@ -0,0 +1,285 @@
+use std::{env, io::IsTerminal, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{
+    aoc_client::DEFAULT_AOC_URL,
+    event_date::EventDate,
+    problem::{Article, Block, Inline, Problem},
+    solution::Part,
+};
+
+const RESET: &str = "\x1b[0m";
+const HEADING: &str = "\x1b[1;32m";
+const EMPHASIS: &str = "\x1b[1;97m";
+const CODE: &str = "\x1b[36m";
+const EMPHASIZED_CODE: &str = "\x1b[1;96m";
+const LINK: &str = "\x1b[4m";
+const DIM: &str = "\x1b[2m";
+const MAX_WIDTH: usize = 100;
+
+/// Renders the puzzle description as plain text wrapped to `width` columns,
+/// styled with ANSI escapes when `color` is set.
+pub struct Ansi {
+    pub color: bool,
+    pub width: usize,
+}
+
+/// A run of text with a single style, `breakable` when it may wrap at its spaces.
+struct Span<'a> {
+    text: &'a str,
+    style: Option<&'static str>,
+    breakable: bool,
+}
+
+fn spans(inlines: &[Inline]) -> Vec<Span<'_>> {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => Span {
+                text,
+                style: None,
+                breakable: true,
+            },
+            Inline::Emphasis(text) => Span {
+                text,
+                style: Some(EMPHASIS),
+                breakable: true,
+            },
+            Inline::Code { text, emphasized } => Span {
+                text,
+                style: Some(if *emphasized { EMPHASIZED_CODE } else { CODE }),
+                breakable: false,
+            },
+            Inline::Link { text, .. } => Span {
+                text,
+                style: Some(LINK),
+                breakable: true,
+            },
+        })
+        .collect()
+}
+
+impl Ansi {
+    /// Colored when stdout is a terminal, as wide as `COLUMNS` up to 100 columns.
+    pub fn for_stdout() -> Self {
+        let width = env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .map_or(80, |columns: usize| columns.min(MAX_WIDTH));
+        Ansi {
+            color: std::io::stdout().is_terminal(),
+            width,
+        }
+    }
+
+    fn styled(&self, text: &str, style: Option<&str>) -> String {
+        match style {
+            Some(style) if self.color && !text.is_empty() => format!("{style}{text}{RESET}"),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Splits the spans into styled words along with their visible widths.
+    fn words(&self, spans: &[Span]) -> Vec<(String, usize)> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut width = 0;
+        for span in spans {
+            let pieces = if span.breakable {
+                span.text.split(char::is_whitespace).collect::<Vec<_>>()
+            } else {
+                vec![span.text]
+            };
+            for (i, piece) in pieces.into_iter().enumerate() {
+                if i > 0 && width > 0 {
+                    words.push((std::mem::take(&mut word), width));
+                    width = 0;
+                }
+                word.push_str(&self.styled(piece, span.style));
+                width += piece.chars().count();
+            }
+        }
+        if width > 0 {
+            words.push((word, width));
+        }
+        words
+    }
+
+    /// Greedily wraps the spans, prefixing the first line with `first_indent`
+    /// and the rest with `indent`, both of visible width `indent_width`.
+    fn wrap(
+        &self,
+        spans: &[Span],
+        first_indent: &str,
+        indent: &str,
+        indent_width: usize,
+    ) -> String {
+        let mut lines = vec![first_indent.to_string()];
+        let mut line_width = 0;
+        for (word, width) in self.words(spans) {
+            if line_width > 0 && indent_width + line_width + 1 + width > self.width {
+                lines.push(indent.to_string());
+                line_width = 0;
+            }
+            let line = lines.last_mut().unwrap();
+            if line_width > 0 {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(&word);
+            line_width += width;
+        }
+        lines.join("\n")
+    }
+
+    fn article(&self, article: &Article) -> String {
+        let mut blocks = vec![self.styled(&format!("--- {} ---", article.heading), Some(HEADING))];
+        for block in &article.blocks {
+            let rendered = match block {
+                Block::Paragraph(inlines) => self.wrap(&spans(inlines), "", "", 0),
+                Block::List(items) => items
+                    .iter()
+                    .map(|item| self.wrap(&spans(item), "  • ", "    ", 4))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Block::Code(code) => code
+                    .lines()
+                    .map(|line| format!("    {}", self.styled(line, Some(CODE))))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            };
+            blocks.push(rendered);
+        }
+        blocks.join("\n\n")
+    }
+
+    pub fn render(&self, problem: &Problem, parts: &[Part]) -> String {
+        let separator = self.styled(&"─".repeat(self.width), Some(DIM));
+        problem
+            .articles
+            .iter()
+            .zip(0..)
+            .filter(|(article, _)| parts.contains(&article.part))
+            .map(|(article, i)| {
+                let mut rendered = self.article(article);
+                if let Some(answer) = problem.answers.get(i) {
+                    rendered.push_str(&format!(
+                        "\n\nYour puzzle answer was {}.",
+                        self.styled(answer, Some(EMPHASIZED_CODE))
+                    ));
+                }
+                rendered
+            })
+            .collect::<Vec<_>>()
+            .join(&format!("\n\n{separator}\n\n"))
+            + "\n"
+    }
+}
+
+static WHITESPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+/// Escapes `text` and collapses its whitespace, since Markdown paragraphs are
+/// reflowed anyway.
+fn escape_markdown(text: &str) -> String {
+    let text = WHITESPACE_RE.replace_all(text, " ");
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A backtick fence longer than any run of backticks in `text`.
+fn fence(text: &str, min_len: usize) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(min_len.max(longest_run + 1))
+}
+
+fn inline_code(text: &str) -> String {
+    let fence = fence(text, 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
+
+/// Resolves links relative to the puzzle page, e.g. `1/input`, to absolute URLs.
+fn absolute_url(href: &str, date: &EventDate) -> String {
+    if href.contains("://") {
+        href.to_string()
+    } else if let Some(path) = href.strip_prefix('/') {
+        format!("{DEFAULT_AOC_URL}/{path}")
+    } else {
+        format!("{DEFAULT_AOC_URL}/{}/day/{href}", date.year)
+    }
+}
+
+fn markdown_inlines(inlines: &[Inline], date: &EventDate) -> String {
+    let markdown = inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape_markdown(text),
+            Inline::Emphasis(text) if text.trim().is_empty() => text.clone(),
+            Inline::Emphasis(text) => format!("**{}**", escape_markdown(text)),
+            Inline::Code {
+                text,
+                emphasized: false,
+            } => inline_code(text),
+            Inline::Code {
+                text,
+                emphasized: true,
+            } => format!("**{}**", inline_code(text)),
+            Inline::Link { text, href } => {
+                format!("[{}]({})", escape_markdown(text), absolute_url(href, date))
+            }
+        })
+        .collect::<String>();
+    markdown.trim().to_string()
+}
+
+/// Converts the puzzle description to Markdown, e.g. to keep it next to the
+/// solution.
+pub fn markdown(problem: &Problem, date: &EventDate) -> String {
+    let mut sections = vec![format!(
+        "# Day {}: {}\n\n<{DEFAULT_AOC_URL}/{}/day/{}>",
+        date.day,
+        escape_markdown(&problem.title),
+        date.year,
+        date.day
+    )];
+    for (i, article) in problem.articles.iter().enumerate() {
+        let heading = match article.part {
+            Part::One => "## Part One",
+            Part::Two => "## Part Two",
+        };
+        let mut blocks = vec![heading.to_string()];
+        for block in &article.blocks {
+            blocks.push(match block {
+                Block::Paragraph(inlines) => markdown_inlines(inlines, date),
+                Block::List(items) => items
+                    .iter()
+                    .map(|item| format!("- {}", markdown_inlines(item, date)))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Block::Code(code) => {
+                    let fence = fence(code, 3);
+                    format!("{fence}text\n{code}\n{fence}")
+                }
+            });
+        }
+        if let Some(answer) = problem.answers.get(i) {
+            blocks.push(format!("Your puzzle answer was {}.", inline_code(answer)));
+        }
+        sections.push(blocks.join("\n\n"));
+    }
+    sections.join("\n\n") + "\n"
+}
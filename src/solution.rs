@@ -1,16 +1,22 @@
 use anyhow::anyhow;
-use regex::Regex;
 use std::{
     fmt::Display,
-    io::{BufRead, BufReader, Read, Write as _},
+    fs,
+    io::{ErrorKind, Read, Write as _},
     path::PathBuf,
+    process::{ExitStatus, Stdio},
     str::FromStr,
     time::Duration,
 };
 
-use crate::{event_date::EventDate, timing};
+use crate::{
+    bench::{self, Budget, Measurement, ParseFn, Stats},
+    event_date::EventDate,
+    protocol::{self, Frame},
+    timing,
+};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Part {
     One,
     Two,
@@ -49,77 +55,169 @@ pub fn bin_name(date: &EventDate) -> String {
     format!("day_{:02}_{}", date.day, date.year)
 }
 
+/// The date of a solution binary or file stem like `day_01_2025`.
+pub fn parse_bin_name(name: &str) -> Option<EventDate> {
+    let (day, year) = name.strip_prefix("day_")?.split_once('_')?;
+    Some(EventDate {
+        day: day.parse().ok()?,
+        year: year.parse().ok()?,
+    })
+}
+
 pub fn solution_path(date: &EventDate) -> PathBuf {
     let bin_name = bin_name(date);
     PathBuf::from("src/solutions").join(format!("{bin_name}.rs"))
 }
 
+/// The Markdown copy of the problem statement kept next to the solution.
+pub fn readme_path(date: &EventDate) -> PathBuf {
+    solution_path(date).with_extension("md")
+}
+
 pub fn read_input() -> anyhow::Result<String> {
     let mut result = String::new();
     std::io::stdin().read_to_string(&mut result)?;
     Ok(result)
 }
 
-pub fn read_run_args() -> Result<Part, anyhow::Error> {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() != 3 || args[1] != "--part" || !"12".contains(&args[2]) {
-        return Err(anyhow::anyhow!("Usage: <cmd> --part <1|2>"));
+/// The part to run, and the budget to benchmark it for when `aor bench` runs
+/// the solution.
+pub fn read_run_args() -> Result<(Part, Option<Budget>), anyhow::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let usage = || anyhow!("Usage: <cmd> --part <1|2> [--iterations <N> | --time <DURATION>]");
+    let (part, budget) = match args.as_slice() {
+        ["--part", part] => (part, None),
+        ["--part", part, "--iterations", iterations] => (
+            part,
+            Some(Budget::Iterations(iterations.parse().map_err(|_| usage())?)),
+        ),
+        ["--part", part, "--time", time] => {
+            (part, Some(Budget::Time(bench::parse_duration(time)?)))
+        }
+        _ => return Err(usage()),
     };
-    let part = if args[2] == "1" { Part::One } else { Part::Two };
-    Ok(part)
+    Ok((part.parse().map_err(|_| usage())?, budget))
 }
 
 pub enum ExecResult {
     Complete {
         answer: String,
-        duration_secs: u64,
-        duration_subsec_ns: u32,
+        duration: Duration,
+        metrics: Vec<(String, f64)>,
     },
     Failed(String),
 }
 
 impl ExecResult {
-    pub fn parse(stdout: &str, stderr: &str) -> anyhow::Result<Self> {
-        let duration_re = Regex::new(r"Duration: (\d+)s, (\d+)ns")?;
-        let caps = duration_re
-            .captures(stderr)
-            .ok_or(anyhow!("Execution duration not printed to stderr"))?;
-        let duration_secs: u64 = caps[1].parse()?;
-        let duration_subsec_ns: u32 = caps[2].parse()?;
-        Ok(ExecResult::Complete {
-            answer: stdout.trim().to_string(),
-            duration_secs,
-            duration_subsec_ns,
-        })
+    /// Interprets what a solution reported before exiting with `status`.
+    pub fn from_frames(frames: Vec<Frame>, status: ExitStatus) -> Self {
+        let mut metrics = Vec::new();
+        let mut result = None;
+        for frame in frames {
+            match frame {
+                Frame::Metric { name, value } => metrics.push((name, value)),
+                Frame::Answer { answer, duration } => result = Some(Ok((answer, duration))),
+                Frame::Error { message } => result = Some(Err(message)),
+                Frame::Panic { message } => result = Some(Err(format!("Panicked: {message}"))),
+                Frame::Samples { .. } => {}
+            }
+        }
+        match result {
+            Some(Ok((answer, duration))) if status.success() => ExecResult::Complete {
+                answer,
+                duration,
+                metrics,
+            },
+            Some(Err(message)) => ExecResult::Failed(message),
+            _ => ExecResult::Failed(format!("Exited with {status} without reporting an answer")),
+        }
     }
 }
 
-pub fn write_answer(answer: &str, duration: Duration) {
-    println!("{}", answer);
-    eprintln!(
-        "Duration: {}s, {}ns",
-        duration.as_secs(),
-        duration.subsec_nanos()
-    );
+/// Reports the answer to `aor`, or prints it when the solution is run by hand.
+pub fn write_answer(answer: &str, duration: Duration) -> anyhow::Result<()> {
+    let frame = Frame::Answer {
+        answer: answer.to_string(),
+        duration,
+    };
+    if !protocol::send(&frame)? {
+        println!("{answer}");
+        eprintln!("Duration: {duration:?}");
+    }
+    Ok(())
 }
 
-type PartFn = fn(String) -> anyhow::Result<String>;
+pub type PartFn = fn(String) -> anyhow::Result<String>;
+
+/// Reports an error to `aor` and exits, or returns it to be printed when the
+/// solution is run by hand.
+fn fail(err: anyhow::Error) -> anyhow::Result<()> {
+    let message = format!("{err:?}");
+    if protocol::send(&Frame::Error { message })? {
+        std::process::exit(1);
+    }
+    Err(err)
+}
 
 pub fn solution_main(part1: PartFn, part2: PartFn) -> anyhow::Result<()> {
-    let part = read_run_args()?;
+    run_solution(part1, part2, None)
+}
+
+/// Like [`solution_main`], but `aor bench` also times `parse` on its own, see
+/// [`parse_fn!`](crate::parse_fn).
+pub fn solution_main_with_parse(
+    part1: PartFn,
+    part2: PartFn,
+    parse: ParseFn,
+) -> anyhow::Result<()> {
+    run_solution(part1, part2, Some(parse))
+}
+
+fn run_solution(part1: PartFn, part2: PartFn, parse: Option<ParseFn>) -> anyhow::Result<()> {
+    let (part, budget) = read_run_args()?;
     let input = read_input()?;
+    protocol::report_panics();
     let solve = match part {
         Part::One => part1,
         Part::Two => part2,
     };
+    if let Some(budget) = budget {
+        let measurements = match bench::bench_part(solve, parse, &input, budget) {
+            Ok(measurements) => measurements,
+            Err(err) => return fail(err),
+        };
+        for Measurement { phase, samples } in measurements {
+            let stats = Stats::new(&samples);
+            if !protocol::send(&Frame::Samples { phase, samples })?
+                && let Some(stats) = stats
+            {
+                println!("{phase}: {stats}");
+            }
+        }
+        return Ok(());
+    }
     let result = timing::it(|| solve(input));
-    write_answer(&result.result?, result.duration);
-    Ok(())
+    match result.result {
+        Ok(answer) => write_answer(&answer, result.duration),
+        Err(err) => fail(err),
+    }
 }
 
 pub fn build(date: &EventDate, release_build: bool) -> anyhow::Result<()> {
-    let bin_name = bin_name(date);
-    let mut args = vec!["build", "--quiet", "--bin", &bin_name];
+    build_all(std::slice::from_ref(date), release_build)
+}
+
+/// Builds the solution binaries for every date in one cargo invocation.
+pub fn build_all(dates: &[EventDate], release_build: bool) -> anyhow::Result<()> {
+    if dates.is_empty() {
+        return Ok(());
+    }
+    let bin_names = dates.iter().map(bin_name).collect::<Vec<_>>();
+    let mut args = vec!["build", "--quiet"];
+    for bin_name in &bin_names {
+        args.extend(["--bin", bin_name]);
+    }
     if release_build {
         args.push("--release");
     }
@@ -134,13 +232,43 @@ pub fn build(date: &EventDate, release_build: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn exec(
+/// Whether Cargo.toml declares a binary for the solution.
+pub fn has_bin(date: &EventDate) -> anyhow::Result<bool> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    let name = bin_name(date);
+    Ok(bin_names(&manifest).any(|bin| bin == name))
+}
+
+/// The names of the `[[bin]]` tables in a Cargo.toml.
+fn bin_names(manifest: &str) -> impl Iterator<Item = &str> {
+    let mut in_bin = false;
+    manifest.lines().filter_map(move |line| {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_bin = line == "[[bin]]";
+            return None;
+        }
+        if !in_bin {
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "name" {
+            return None;
+        }
+        Some(value.trim().trim_matches('"'))
+    })
+}
+
+/// Runs the solution binary with `args` after the part, returning its exit
+/// status and the frames it reported.
+fn spawn(
     input: &str,
     part: Part,
     date: &EventDate,
     release_build: bool,
-) -> anyhow::Result<ExecResult> {
-    let bin_name = bin_name(&date);
+    args: &[String],
+) -> anyhow::Result<(ExitStatus, Vec<Frame>)> {
+    let bin_name = bin_name(date);
 
     let target = if release_build { "release" } else { "debug" };
 
@@ -148,47 +276,68 @@ pub fn exec(
     let binary_dir: PathBuf = [manifest_dir.as_str(), "target", target].iter().collect();
     let binary_path = binary_dir.join(&bin_name);
 
+    // Stdout and stderr are left to the solution's own output, with stdout
+    // sent to stderr so it can't be mistaken for the answer
+    let report_path = binary_dir.join(format!(
+        "{bin_name}.part{part}.{}.report",
+        std::process::id()
+    ));
     let mut child = std::process::Command::new(&binary_path)
-        .args(&["--part", &part.to_string()])
-        .stdin(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .spawn()?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(input.as_bytes())?;
-    }
+        .args(["--part", &part.to_string()])
+        .args(args)
+        .env(protocol::REPORT_PATH_VAR, &report_path)
+        .stdin(Stdio::piped())
+        .stdout(std::io::stderr())
+        .spawn()
+        .map_err(|err| anyhow!("Couldn't start {}: {err}", binary_path.display()))?;
 
-    let stderr_output = if let Some(mut stderr) = child.stderr.take() {
-        let mut stderr_output = String::new();
-        std::thread::spawn(move || {
-            BufReader::new(&mut stderr)
-                .lines()
-                .for_each(|line| match line {
-                    Ok(l) => {
-                        if !l.starts_with("Duration: ") {
-                            eprintln!("{}", l);
-                        }
-                        stderr_output.push_str(&l);
-                    }
-                    Err(err) => eprintln!("Error reading stderr line {err:?}"),
-                });
-            stderr_output
-        })
-    } else {
-        return Err(anyhow!("Failed to capture stderr from child process"));
+    // A solution that exits without reading its input closes the pipe early,
+    // which still leaves its report to read
+    let written = match child.stdin.take() {
+        Some(mut stdin) => match stdin.write_all(input.as_bytes()) {
+            Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(()),
+            written => written,
+        },
+        None => Ok(()),
     };
+    let status = child.wait();
 
-    let output = child.wait_with_output()?;
+    let frames = protocol::read_report(&report_path);
+    let _ = fs::remove_file(&report_path);
+    written?;
+    Ok((status?, frames?))
+}
 
-    let stderr = stderr_output
-        .join()
-        .map_err(|err| anyhow!("Failed to join stderr listener: {err:?}"))?;
+pub fn exec(
+    input: &str,
+    part: Part,
+    date: &EventDate,
+    release_build: bool,
+) -> anyhow::Result<ExecResult> {
+    let (status, frames) = spawn(input, part, date, release_build, &[])?;
+    Ok(ExecResult::from_frames(frames, status))
+}
 
-    if !output.status.success() {
-        return Ok(ExecResult::Failed(stderr.into()));
+/// Benchmarks `part` in the solution's release binary, which must already be
+/// built.
+pub fn exec_bench(
+    input: &str,
+    part: Part,
+    date: &EventDate,
+    budget: Budget,
+) -> anyhow::Result<Vec<Measurement>> {
+    let (status, frames) = spawn(input, part, date, true, &budget.to_args())?;
+    let mut measurements = Vec::new();
+    for frame in frames {
+        match frame {
+            Frame::Samples { phase, samples } => measurements.push(Measurement { phase, samples }),
+            Frame::Error { message } => return Err(anyhow!(message)),
+            Frame::Panic { message } => return Err(anyhow!("Panicked: {message}")),
+            Frame::Metric { .. } | Frame::Answer { .. } => {}
+        }
     }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    ExecResult::parse(&stdout, &stderr)
+    if !status.success() || measurements.is_empty() {
+        return Err(anyhow!("Exited with {status} without reporting timings"));
+    }
+    Ok(measurements)
 }
@@ -26,6 +26,8 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(result.to_string())
 }
 
+aor::register_solution!(part1, part2);
+
 fn main() -> anyhow::Result<()> {
     solution_main(part1, part2)
 }
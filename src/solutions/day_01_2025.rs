@@ -1,4 +1,4 @@
-use aor::solution::solution_main;
+use aor::solution::solution_main_with_parse;
 
 /// Day 1, 2025 | https://adventofcode.com/2025/day/1
 
@@ -44,8 +44,10 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(passed_zero.to_string())
 }
 
+aor::register_solution!(part1, part2, parse);
+
 fn main() -> anyhow::Result<()> {
-    solution_main(part1, part2)
+    solution_main_with_parse(part1, part2, aor::parse_fn!(parse))
 }
 
 #[cfg(test)]
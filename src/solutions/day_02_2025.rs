@@ -92,6 +92,8 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(invalid_ids.iter().sum::<u64>().to_string())
 }
 
+aor::register_solution!(part1, part2);
+
 fn main() -> anyhow::Result<()> {
     solution_main(part1, part2)
 }
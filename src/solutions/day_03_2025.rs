@@ -57,6 +57,8 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(result)
 }
 
+aor::register_solution!(part1, part2);
+
 fn main() -> anyhow::Result<()> {
     solution_main(part1, part2)
 }
@@ -66,6 +66,8 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(removed.to_string())
 }
 
+aor::register_solution!(part1, part2);
+
 fn main() -> anyhow::Result<()> {
     solution_main(part1, part2)
 }
@@ -1,6 +1,6 @@
 #![feature(range_into_bounds)]
 
-use aor::solution::solution_main;
+use aor::solution::solution_main_with_parse;
 use itertools::Itertools;
 
 /// Day 5, 2025 | https://adventofcode.com/2025/day/5
@@ -63,8 +63,10 @@ fn part2(input: String) -> anyhow::Result<String> {
     Ok(total_size.to_string())
 }
 
+aor::register_solution!(part1, part2, |input: String| parse(&input));
+
 fn main() -> anyhow::Result<()> {
-    solution_main(part1, part2)
+    solution_main_with_parse(part1, part2, aor::parse_fn!(|input: String| parse(&input)))
 }
 
 #[cfg(test)]
@@ -0,0 +1,8 @@
+use crate::{aoc_client, profile::Profile};
+
+pub fn check_auth() -> anyhow::Result<()> {
+    let profile = Profile::selected();
+    let user = aoc_client::get_user(profile)?;
+    println!("Logged in as {user} (profile: {profile})");
+    Ok(())
+}
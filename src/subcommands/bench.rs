@@ -0,0 +1,153 @@
+use std::{fs, process::ExitCode};
+
+use anyhow::anyhow;
+use itertools::Itertools as _;
+
+use crate::{
+    aoc_client,
+    bench::{Budget, Comparison, Measurement, Stats},
+    bench_history::{self, Baseline, BenchHistory, BenchRecord},
+    event_date::EventDate,
+    registry,
+    solution::{self, Part, solution_path},
+    table::Table,
+};
+
+fn format_stats(stats: &Stats) -> [String; 6] {
+    [
+        stats.runs.to_string(),
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.mean),
+        format!("{:.2?}", stats.stddev),
+        format!("{:.2?}", stats.p95),
+    ]
+}
+
+/// Benchmarks the solution's parts and records the results. With a baseline,
+/// each phase is compared with its latest earlier run, failing when one is
+/// significantly slower by more than `fail_above` percent.
+pub fn bench(
+    day: Option<u8>,
+    year: Option<u16>,
+    parts: &[Part],
+    budget: Budget,
+    baseline: Option<Baseline>,
+    fail_above: Option<f64>,
+    save: bool,
+) -> anyhow::Result<ExitCode> {
+    let date = EventDate::create_or_default(day, year);
+    let input = aoc_client::get_input(&date)?;
+
+    let registered = registry::find_linked(&date)?;
+    // Debug builds are too slow to compare with anything
+    let save = save && !(registered.is_some() && cfg!(debug_assertions));
+    match registered {
+        Some(_) if cfg!(debug_assertions) => eprintln!(
+            "Benchmarking in a debug build of aor, run it with --release for meaningful timings"
+        ),
+        Some(_) => {}
+        None => solution::build(&date, true)?,
+    }
+
+    let history_path = bench_history::history_path();
+    let mut history = BenchHistory::load(&history_path)?;
+    let commit = bench_history::current_commit();
+    let source_hash = bench_history::source_hash(&fs::read(solution_path(&date))?);
+    let baseline = baseline.or(fail_above.map(|_| Baseline::Previous));
+    let committed_hash = match &baseline {
+        Some(Baseline::Commit(commit)) => {
+            bench_history::committed_source_hash(commit, &solution_path(&date))
+        }
+        _ => None,
+    };
+
+    let mut headers = vec![
+        "Part", "Phase", "Runs", "Min", "Median", "Mean", "Std dev", "p95",
+    ];
+    if baseline.is_some() {
+        headers.extend(["Baseline", "Change"]);
+    }
+    let mut table = Table::new(headers);
+    let mut records = Vec::new();
+    let mut regressions = Vec::new();
+    let mut failed = Vec::new();
+    for &part in parts {
+        let measurements = match registered {
+            Some(registered) => registered.bench(part, &input, budget),
+            None => solution::exec_bench(&input, part, &date, budget),
+        };
+        let measurements = match measurements {
+            Ok(measurements) => measurements,
+            Err(err) => {
+                eprintln!("Part {part} failed: {err}");
+                failed.push(part);
+                continue;
+            }
+        };
+        for Measurement { phase, samples } in measurements {
+            let Some(stats) = Stats::new(&samples) else {
+                continue;
+            };
+            let record = BenchRecord::new(
+                &date,
+                part,
+                phase,
+                commit.clone(),
+                source_hash.clone(),
+                stats,
+            );
+            let mut row = vec![part.to_string(), phase.to_string()];
+            row.extend(format_stats(&stats));
+            if let Some(baseline) = &baseline {
+                match history.baseline(&record, baseline, committed_hash.as_deref()) {
+                    Some(earlier) => {
+                        let comparison = Comparison::new(&stats, &earlier.stats);
+                        row.push(format!(
+                            "{:.2?} at {} (source {})",
+                            earlier.stats.mean,
+                            earlier.commit.as_deref().unwrap_or("unknown commit"),
+                            &earlier.source_hash[..earlier.source_hash.len().min(8)]
+                        ));
+                        row.push(comparison.to_string());
+                        if let Some(threshold) = fail_above
+                            && comparison.significant()
+                            && comparison.slowdown_percent() > threshold
+                        {
+                            regressions.push(format!(
+                                "Part {part} {phase} is {:.1}% slower than the baseline",
+                                comparison.slowdown_percent()
+                            ));
+                        }
+                    }
+                    None => row.extend(["-".to_string(), "no baseline".to_string()]),
+                }
+            }
+            table.push_row(row);
+            records.push(record);
+        }
+    }
+
+    println!("Day {} {}\n", date.day, date.year);
+    print!("{table}");
+    if save && !records.is_empty() {
+        history.records.extend(records);
+        history.save(&history_path)?;
+    }
+    if !failed.is_empty() {
+        return Err(anyhow!(
+            "Benchmarking Part {} failed",
+            failed.iter().join(" and ")
+        ));
+    }
+    if !regressions.is_empty() {
+        for regression in &regressions {
+            eprintln!(
+                "{regression} (threshold {}%)",
+                fail_above.unwrap_or_default()
+            );
+        }
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
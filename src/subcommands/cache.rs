@@ -0,0 +1,153 @@
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Local};
+use itertools::Itertools as _;
+
+use crate::{
+    cache::{self, EntryKind},
+    event_date::EventDate,
+    profile::Profile,
+    submission::SubmissionHistory,
+    table::Table,
+};
+
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f < KB {
+        format!("{bytes} B")
+    } else if bytes_f < KB * KB {
+        format!("{:.1} KB", bytes_f / KB)
+    } else {
+        format!("{:.1} MB", bytes_f / (KB * KB))
+    }
+}
+
+fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s ago"),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+fn describe(path: &Path) -> anyhow::Result<String> {
+    let metadata = fs::metadata(path)?;
+    let age = metadata.modified()?.elapsed().unwrap_or_default();
+    Ok(format!(
+        "{}, {}",
+        format_size(metadata.len()),
+        format_age(age)
+    ))
+}
+
+pub fn cache_list(year: Option<u16>) -> anyhow::Result<()> {
+    let cache_dir = Profile::selected().cache_dir()?;
+    let entries = cache::entries(&cache_dir, year)?;
+    println!("Cache: {}\n", cache_dir.display());
+
+    let mut table = Table::new(["Date", "Problem", "Input", "Submissions"]);
+    for (date, entries) in &entries.iter().chunk_by(|entry| entry.date) {
+        let entries = entries.collect::<Vec<_>>();
+        let mut row = vec![format!("{}-12-{:02}", date.year, date.day)];
+        for kind in EntryKind::ALL {
+            let cell = match entries.iter().find(|entry| entry.kind == kind) {
+                Some(entry) => describe(&entry.path)?,
+                None => "-".to_string(),
+            };
+            row.push(cell);
+        }
+        table.push_row(row);
+    }
+    print!("{table}");
+    Ok(())
+}
+
+pub fn cache_show(day: Option<u8>, year: Option<u16>) -> anyhow::Result<()> {
+    let date = EventDate::create_or_default(day, year);
+    let cache_dir = Profile::selected().cache_dir()?;
+    println!("Day {}, {}", date.day, date.year);
+
+    for kind in EntryKind::ALL {
+        let entry = cache::Entry {
+            date,
+            kind,
+            path: kind.path(&cache_dir, &date),
+        };
+        if !entry.path.exists() {
+            println!("  {kind}: not cached");
+            continue;
+        }
+        let status = match entry.verify() {
+            Ok(()) => "ok".to_string(),
+            Err(err) => format!("suspicious, {err}"),
+        };
+        println!(
+            "  {kind}: {} ({}, {status})",
+            entry.path.display(),
+            describe(&entry.path)?
+        );
+    }
+
+    for submission in SubmissionHistory::load(&date)?.submissions {
+        let submitted_at = DateTime::from_timestamp(submission.submitted_at, 0)
+            .map(|time| {
+                time.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        println!(
+            "    {submitted_at}  Part {}  {}  {:?}",
+            submission.part, submission.answer, submission.verdict
+        );
+    }
+    Ok(())
+}
+
+pub fn cache_purge(year: u16, day: Option<u8>, kinds: &[EntryKind]) -> anyhow::Result<()> {
+    let cache_dir = Profile::selected().cache_dir()?;
+    let entries = cache::entries(&cache_dir, Some(year))?
+        .into_iter()
+        .filter(|entry| day.is_none_or(|day| day == entry.date.day))
+        .filter(|entry| kinds.is_empty() || kinds.contains(&entry.kind));
+
+    let mut removed = 0;
+    for entry in entries {
+        fs::remove_file(&entry.path)?;
+        println!("Removed {}", entry.path.display());
+        removed += 1;
+    }
+    println!("Removed {removed} cached entries");
+    Ok(())
+}
+
+pub fn cache_verify() -> anyhow::Result<()> {
+    let cache_dir = Profile::selected().cache_dir()?;
+    let entries = cache::entries(&cache_dir, None)?;
+
+    let mut suspicious = 0;
+    for entry in &entries {
+        if let Err(err) = entry.verify() {
+            eprintln!(
+                "Day {}, {} {}: {err} ({})",
+                entry.date.day,
+                entry.date.year,
+                entry.kind,
+                entry.path.display()
+            );
+            suspicious += 1;
+        }
+    }
+
+    println!("Checked {} cached entries", entries.len());
+    if suspicious > 0 {
+        return Err(anyhow!(
+            "Found {suspicious} suspicious entries, remove them with `aor cache purge`"
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use std::{io::Write, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+/// Blocks until `until`, printing the remaining seconds after `label` on a single line.
+pub fn countdown(until: DateTime<Utc>, label: &str) -> anyhow::Result<()> {
+    let seconds = (until - Utc::now()).num_seconds() + 1;
+
+    for remaining_seconds in (1..=seconds).rev() {
+        print!("\x1b[2K\r{label}: {remaining_seconds}s");
+        std::io::stdout().flush()?;
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    print!("\x1b[2K\r");
+    std::io::stdout().flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+
+use crate::{
+    aoc_client::{self, AuthError},
+    event_date::{DayRange, EventDate},
+};
+
+struct Resource {
+    name: &'static str,
+    is_cached: fn(&EventDate) -> bool,
+    fetch: fn(&EventDate) -> anyhow::Result<String>,
+}
+
+const RESOURCES: [Resource; 2] = [
+    Resource {
+        name: "problem",
+        is_cached: aoc_client::is_problem_cached,
+        fetch: aoc_client::get_problem,
+    },
+    Resource {
+        name: "input",
+        is_cached: aoc_client::is_input_cached,
+        fetch: aoc_client::get_input,
+    },
+];
+
+/// Parses a delay given in seconds, e.g. `0.5`.
+pub fn parse_delay(s: &str) -> anyhow::Result<Duration> {
+    let seconds: f64 = s
+        .parse()
+        .map_err(|_| anyhow!("{s} is not a number of seconds"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow!("{s} is not a valid delay"))
+}
+
+/// Sleeps until at least `delay` has passed since the last request.
+fn throttle(last_request: &mut Option<Instant>, delay: Duration) {
+    if let Some(last_request) = last_request {
+        let elapsed = last_request.elapsed();
+        if elapsed < delay {
+            std::thread::sleep(delay - elapsed);
+        }
+    }
+    *last_request = Some(Instant::now());
+}
+
+pub fn fetch(year: Option<u16>, days: Option<DayRange>, delay: Duration) -> anyhow::Result<()> {
+    let year = year.unwrap_or_else(|| EventDate::default().year);
+    let last_day = EventDate::days_in_year(year);
+    let days = days.map_or(1..=last_day, |DayRange(days)| days);
+    if *days.end() > last_day {
+        return Err(anyhow!("{year} only has {last_day} days"));
+    }
+
+    let mut fetched = 0;
+    let mut cached = 0;
+    let mut locked = 0;
+    let mut failed = 0;
+    let mut last_request = None;
+
+    for day in days {
+        let date = EventDate { day, year };
+        if date.ensure_unlocked().is_err() {
+            locked += 1;
+            continue;
+        }
+        for resource in &RESOURCES {
+            if (resource.is_cached)(&date) {
+                cached += 1;
+                continue;
+            }
+            throttle(&mut last_request, delay);
+            match (resource.fetch)(&date) {
+                Ok(_) => {
+                    println!("Fetched day {day} {}", resource.name);
+                    fetched += 1;
+                }
+                Err(err) if err.is::<AuthError>() => return Err(err),
+                Err(err) => {
+                    eprintln!("Failed to fetch day {day} {}: {err}", resource.name);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!(
+        "{year}: fetched {fetched}, already cached {cached}, failed {failed}, {locked} days still locked"
+    );
+    if failed > 0 {
+        return Err(anyhow!("Failed to fetch {failed} resources"));
+    }
+    Ok(())
+}
@@ -1,12 +1,13 @@
-use std::{fs, io::Write, path::Path, time::Duration};
+use std::{fs, io::Write, path::Path};
 
+use super::{countdown::countdown, readme::write_readme};
 use crate::{
     aoc_client,
     event_date::EventDate,
     solution::{self, solution_path},
 };
 use anyhow::anyhow;
-use chrono::{Local, TimeZone};
+use chrono::{Local, TimeZone, Utc};
 use chrono_tz;
 
 fn block_and_countdown(until_date: &EventDate) -> anyhow::Result<()> {
@@ -20,15 +21,7 @@ fn block_and_countdown(until_date: &EventDate) -> anyhow::Result<()> {
         return Err(anyhow!("Can't countdown to event in the past"));
     }
 
-    let seconds = (start - now).num_seconds() + 1;
-
-    for remaining_seconds in (1..=seconds).rev() {
-        print!("\x1b[2K\rInitializing in: {remaining_seconds}s");
-        std::io::stdout().flush().unwrap();
-        std::thread::sleep(Duration::from_secs(1));
-    }
-
-    Ok(())
+    countdown(start.with_timezone(&Utc), "Initializing in")
 }
 
 fn maybe_open_in_editor(path: &Path) -> anyhow::Result<()> {
@@ -46,6 +39,7 @@ pub fn init(
     year: Option<u16>,
     countdown: bool,
     fetch_input_only: bool,
+    readme: bool,
 ) -> anyhow::Result<()> {
     let date = EventDate::create_or_next(day, year);
     if countdown {
@@ -66,20 +60,28 @@ pub fn init(
             .replace("{{yyyy}}", &date.year.to_string());
         fs::write(&solution_path, template)?;
 
-        // Append to Cargo.toml
-        let bin_entry = &format!(
-            "\n[[bin]]\nname = \"{}\"\npath = \"{}\"\n",
-            solution::bin_name(&date),
-            solution_path.to_string_lossy()
-        );
+        // Registered solutions are linked into `aor` instead of getting a binary
+        if !cfg!(feature = "registry") {
+            let bin_entry = &format!(
+                "\n[[bin]]\nname = \"{}\"\npath = \"{}\"\n",
+                solution::bin_name(&date),
+                solution_path.to_string_lossy()
+            );
 
-        let mut cargo_toml_file = fs::OpenOptions::new().append(true).open("Cargo.toml")?;
-        cargo_toml_file.write_all(bin_entry.as_bytes())?;
+            let mut cargo_toml_file = fs::OpenOptions::new().append(true).open("Cargo.toml")?;
+            cargo_toml_file.write_all(bin_entry.as_bytes())?;
+        }
 
         println!(
             "Initialized solution from template at: {}",
             solution_path.canonicalize()?.to_string_lossy()
         );
+        if cfg!(feature = "registry") {
+            println!("Rebuild aor with --features registry to link it in and run it");
+        }
+        if readme {
+            write_readme(&date)?;
+        }
         maybe_open_in_editor(&solution_path)?;
     }
 
@@ -0,0 +1,63 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::{
+    aoc_client, event_date::EventDate, leaderboard::Leaderboard, solution::Part, table::Table,
+};
+
+fn format_elapsed(elapsed: TimeDelta) -> String {
+    let seconds = elapsed.num_seconds();
+    if seconds < 60 * 60 {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    } else if seconds < 24 * 60 * 60 {
+        format!("{}h{:02}", seconds / (60 * 60), seconds / 60 % 60)
+    } else {
+        format!("{}d", seconds / (24 * 60 * 60))
+    }
+}
+
+pub fn leaderboard(id: u64, year: Option<u16>) -> anyhow::Result<()> {
+    let year = year.unwrap_or_else(|| EventDate::default().year);
+    let leaderboard = Leaderboard::parse(&aoc_client::get_private_leaderboard(year, id)?)?;
+    let days = 1..=leaderboard.last_day().unwrap_or(0);
+    let unlock_times = days
+        .clone()
+        .map(|day| {
+            let unlock: DateTime<chrono_tz::Tz> = EventDate { day, year }.try_into()?;
+            Ok(unlock.with_timezone(&Utc))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut table = Table::new(
+        ["#", "Name", "Stars", "Score"]
+            .map(String::from)
+            .into_iter()
+            .chain(days.clone().map(|day| day.to_string())),
+    );
+    for (rank, member) in leaderboard.ranked_members().into_iter().enumerate() {
+        let completion_times = days.clone().zip(&unlock_times).map(|(day, unlock)| {
+            match (
+                member.star_time(day, Part::One),
+                member.star_time(day, Part::Two),
+            ) {
+                (_, Some(time)) => format!("{}**", format_elapsed(time - unlock)),
+                (Some(time), None) => format!("{}*", format_elapsed(time - unlock)),
+                (None, _) => String::new(),
+            }
+        });
+        table.push_row(
+            [
+                (rank + 1).to_string(),
+                member.display_name(),
+                member.stars.to_string(),
+                member.local_score.to_string(),
+            ]
+            .into_iter()
+            .chain(completion_times),
+        );
+    }
+
+    println!("Private leaderboard {id}, {year}\n");
+    print!("{table}");
+    println!("\nTimes are since each day unlocked, * for one star and ** for both");
+    Ok(())
+}
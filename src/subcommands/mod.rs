@@ -1,8 +1,24 @@
+mod auth;
+pub use self::auth::check_auth;
+mod bench;
+pub use self::bench::bench;
+mod cache;
+pub use self::cache::{cache_list, cache_purge, cache_show, cache_verify};
+mod countdown;
+mod fetch;
+pub use self::fetch::{fetch, parse_delay};
 mod init;
 pub use self::init::init;
+mod leaderboard;
+pub use self::leaderboard::leaderboard;
+mod readme;
 mod run;
-pub use self::run::run;
+pub use self::run::{run, run_all};
+mod show;
+pub use self::show::show;
+mod status;
+pub use self::status::status;
 mod submit;
 pub use self::submit::submit;
 mod testgen;
-pub use self::testgen::generate_tests;
+pub use self::testgen::{ExampleSelection, generate_tests};
@@ -0,0 +1,12 @@
+use std::fs;
+
+use crate::{aoc_client, event_date::EventDate, problem::Problem, render, solution::readme_path};
+
+/// Writes the cached problem statement as Markdown next to the solution.
+pub fn write_readme(date: &EventDate) -> anyhow::Result<()> {
+    let problem = Problem::parse(&aoc_client::get_problem(date)?);
+    let path = readme_path(date);
+    fs::write(&path, render::markdown(&problem, date))?;
+    println!("Wrote the problem statement to {}", path.to_string_lossy());
+    Ok(())
+}
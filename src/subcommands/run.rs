@@ -1,9 +1,15 @@
-use std::{fs, io::IsTerminal, path::PathBuf, time::Duration};
+use std::{fmt::Display, fs, io::IsTerminal, path::PathBuf, process::ExitCode, time::Duration};
+
+use anyhow::anyhow;
 
 use crate::{
     aoc_client,
     event_date::EventDate,
-    solution::{self, ExecResult},
+    problem::Problem,
+    profile::Profile,
+    registry,
+    solution::{self, ExecResult, Part},
+    table::Table,
 };
 
 fn get_input(date: &EventDate, override_path: Option<PathBuf>) -> anyhow::Result<String> {
@@ -13,58 +19,255 @@ fn get_input(date: &EventDate, override_path: Option<PathBuf>) -> anyhow::Result
     aoc_client::get_input(date)
 }
 
+fn print_result(result: ExecResult, label: &str) {
+    match result {
+        ExecResult::Complete {
+            answer,
+            duration,
+            metrics,
+        } => {
+            if std::io::stdout().is_terminal() {
+                println!(
+                    "┌ {label}\n{answer}{metrics}\n└ In {duration:#?} ",
+                    answer = answer
+                        .lines()
+                        .map(|line| format!("│  \x1b[32m{line}\x1b[0m"))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    metrics = metrics
+                        .iter()
+                        .map(|(name, value)| format!("\n│  \x1b[2m{name}: {value}\x1b[0m"))
+                        .collect::<String>(),
+                    duration = duration,
+                )
+            } else {
+                println!("{answer}")
+            }
+        }
+        ExecResult::Failed(error) => {
+            if std::io::stderr().is_terminal() {
+                eprintln!(
+                    "\x1b[31m┌ {label} failed\n{error}\n└\x1b[0m",
+                    error = error
+                        .lines()
+                        .map(|line| format!("│  {line}"))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+            } else {
+                eprintln!("{error}")
+            }
+        }
+    }
+}
+
+/// How an answer compares with the one AoC accepted for its part.
+enum Check {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// The part isn't solved yet or its problem isn't cached
+    Unknown,
+}
+
+impl Check {
+    fn new(accepted: &[String], part: Part, answer: &str) -> Self {
+        match accepted.get(part.to_int() as usize - 1) {
+            Some(accepted) if accepted == answer => Check::Correct,
+            Some(accepted) => Check::Wrong {
+                expected: accepted.clone(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => write!(f, "correct"),
+            Check::Wrong { expected } => write!(f, "wrong, expected {expected}"),
+            Check::Unknown => write!(f, "unverified"),
+        }
+    }
+}
+
+/// Runs the solution for one day. With `all_profiles` it runs against every
+/// profile's input and fails when the solution fails on one or an answer
+/// differs from the one AoC accepted for that profile.
 pub fn run(
     day: Option<u8>,
     year: Option<u16>,
     parts: &[solution::Part],
     input_override_path: Option<PathBuf>,
     release_build: bool,
-) -> anyhow::Result<()> {
+    all_profiles: bool,
+) -> anyhow::Result<ExitCode> {
     let date = EventDate::create_or_default(day, year);
-    let input = get_input(&date, input_override_path)?;
-
-    solution::build(&date, release_build)?;
-
-    for &part in parts {
-        let result = solution::exec(&input, part, &date, release_build)?;
-
-        match result {
-            ExecResult::Complete {
-                answer,
-                duration_secs,
-                duration_subsec_ns,
-            } => {
-                if std::io::stdout().is_terminal() {
-                    let duration = Duration::new(duration_secs, duration_subsec_ns);
-                    println!(
-                        "┌ Part {part}\n{answer}\n└ In {duration:#?} ",
-                        answer = answer
-                            .lines()
-                            .map(|line| format!("│  \x1b[32m{line}\x1b[0m"))
-                            .collect::<Vec<_>>()
-                            .join("\n"),
-                        duration = duration,
-                    )
-                } else {
-                    println!("{answer}")
+
+    let inputs = if all_profiles {
+        let profiles = Profile::all();
+        if profiles.is_empty() {
+            return Err(anyhow!(
+                "No profiles to run against, set AOC_AUTH_TOKEN or AOC_AUTH_TOKEN_<PROFILE>"
+            ));
+        }
+        profiles
+            .into_iter()
+            .map(|profile| {
+                let input = aoc_client::get_input_for(&date, &profile)?;
+                let accepted =
+                    Problem::parse(&aoc_client::get_problem_for(&date, &profile)?).answers;
+                Ok((Some(profile), input, accepted))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+    } else {
+        vec![(None, get_input(&date, input_override_path)?, Vec::new())]
+    };
+
+    let registered = registry::find_linked(&date)?;
+    if registered.is_none() {
+        solution::build(&date, release_build)?;
+    }
+
+    let mut failures = 0;
+    for (profile, input, accepted) in &inputs {
+        for &part in parts {
+            let result = match registered {
+                Some(registered) => registered.run(part, input.clone()),
+                None => solution::exec(input, part, &date, release_build)?,
+            };
+            let label = match profile {
+                Some(profile) => format!("Part {part} ({profile})"),
+                None => format!("Part {part}"),
+            };
+            let check = match &result {
+                ExecResult::Complete { answer, .. } => Some(Check::new(accepted, part, answer)),
+                ExecResult::Failed(_) => None,
+            };
+            print_result(result, &label);
+            match check {
+                Some(check @ Check::Wrong { .. }) => {
+                    eprintln!("{label}: {check}");
+                    failures += 1;
                 }
+                Some(_) => {}
+                None => failures += 1,
             }
-            ExecResult::Failed(error) => {
-                if std::io::stderr().is_terminal() {
-                    eprintln!(
-                        "\x1b[31m┌ Part {part} failed\n{error}\n└\x1b[0m",
-                        error = error
-                            .lines()
-                            .map(|line| format!("│  {line}"))
-                            .collect::<Vec<_>>()
-                            .join("\n"),
-                    );
-                } else {
-                    eprintln!("{error}")
+        }
+    }
+
+    if failures > 0 {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The first line of `text`, marked when there's more.
+fn first_line(text: &str) -> String {
+    let mut lines = text.trim().lines();
+    let first = lines.next().unwrap_or_default().to_string();
+    if lines.next().is_some() {
+        format!("{first} …")
+    } else {
+        first
+    }
+}
+
+/// Runs every solution for `year` against its cached input, building the
+/// solution binaries in one go, and prints a summary table. Answers are checked
+/// against the ones accepted by AoC when the problem is cached.
+pub fn run_all(year: Option<u16>, parts: &[Part], release_build: bool) -> anyhow::Result<ExitCode> {
+    let year = year.unwrap_or_else(|| EventDate::default().year);
+    let dates = (1..=EventDate::days_in_year(year))
+        .map(|day| EventDate { day, year })
+        .filter(|date| solution::solution_path(date).exists())
+        .collect::<Vec<_>>();
+    if dates.is_empty() {
+        return Err(anyhow!("No solutions for {year}"));
+    }
+
+    let mut to_build = Vec::new();
+    for date in &dates {
+        if registry::find(date).is_none() && solution::has_bin(date)? {
+            to_build.push(*date);
+        }
+    }
+    solution::build_all(&to_build, release_build)?;
+
+    let mut table = Table::new(["Day", "Part", "Answer", "Time", "Status"]);
+    let mut total = Duration::ZERO;
+    let mut failures = 0;
+    for date in &dates {
+        let registered = registry::find(date);
+        let skipped = if !aoc_client::is_input_cached(date) {
+            Some("no cached input")
+        } else if registered.is_none() && !to_build.contains(date) {
+            Some(if cfg!(feature = "registry") {
+                "not linked into aor, rebuild it"
+            } else {
+                "no binary in Cargo.toml"
+            })
+        } else {
+            None
+        };
+        if let Some(reason) = skipped {
+            table.push_row([
+                date.day.to_string(),
+                "-".into(),
+                "-".into(),
+                "-".into(),
+                reason.into(),
+            ]);
+            continue;
+        }
+
+        let input = aoc_client::get_input(date)?;
+        let accepted = if aoc_client::is_problem_cached(date) {
+            Problem::parse(&aoc_client::get_problem(date)?).answers
+        } else {
+            Vec::new()
+        };
+        for &part in parts {
+            let result = match registered {
+                Some(registered) => registered.run(part, input.clone()),
+                None => solution::exec(&input, part, date, release_build)
+                    .unwrap_or_else(|error| ExecResult::Failed(format!("{error:#}"))),
+            };
+            let (answer, time, status) = match result {
+                ExecResult::Complete {
+                    answer, duration, ..
+                } => {
+                    total += duration;
+                    let check = Check::new(&accepted, part, &answer);
+                    if let Check::Wrong { .. } = check {
+                        failures += 1;
+                    }
+                    (
+                        first_line(&answer),
+                        format!("{duration:.2?}"),
+                        check.to_string(),
+                    )
                 }
-            }
+                ExecResult::Failed(error) => {
+                    failures += 1;
+                    (
+                        "-".to_string(),
+                        "-".to_string(),
+                        format!("failed: {}", first_line(&error)),
+                    )
+                }
+            };
+            table.push_row([date.day.to_string(), part.to_string(), answer, time, status]);
         }
     }
 
-    Ok(())
+    print!("{table}");
+    println!("\nTotal time: {total:.2?}");
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed or gave a wrong answer");
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
 }
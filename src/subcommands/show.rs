@@ -0,0 +1,61 @@
+use std::{
+    env,
+    io::{IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use anyhow::anyhow;
+
+use crate::{aoc_client, event_date::EventDate, problem::Problem, render::Ansi, solution::Part};
+
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Writes `text` through `$PAGER` (`less -R` by default), falling back to
+/// printing it directly if the pager can't be started.
+fn page(text: &str) -> anyhow::Result<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut args = pager.split_whitespace();
+    let spawned = args.next().map(|program| {
+        Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+    });
+    let Some(Ok(mut child)) = spawned else {
+        print!("{text}");
+        return Ok(());
+    };
+    // The pager closing early (e.g. quitting `less` before the end) isn't an error
+    let _ = child.stdin.take().unwrap().write_all(text.as_bytes());
+    child.wait()?;
+    Ok(())
+}
+
+pub fn show(
+    day: Option<u8>,
+    year: Option<u16>,
+    part: Option<Part>,
+    no_pager: bool,
+) -> anyhow::Result<()> {
+    let date = EventDate::create_or_default(day, year);
+    let problem = Problem::parse(&aoc_client::get_problem(&date)?);
+    let parts = match part {
+        Some(part) if problem.article(part).is_none() => {
+            return Err(anyhow!(
+                "Part {part} of day {}, {} isn't unlocked yet",
+                date.day,
+                date.year
+            ));
+        }
+        Some(part) => vec![part],
+        None => problem.unlocked_parts(),
+    };
+
+    let rendered = Ansi::for_stdout().render(&problem, &parts);
+    if std::io::stdout().is_terminal() && !no_pager {
+        page(&rendered)
+    } else {
+        print!("{rendered}");
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    aoc_client, calendar::Calendar, event_date::EventDate, solution::solution_path, table::Table,
+    test_file::SolutionFile,
+};
+
+pub fn status(year: Option<u16>) -> anyhow::Result<()> {
+    let year = year.unwrap_or_else(|| EventDate::default().year);
+    let calendar = Calendar::parse(&aoc_client::get_calendar(year)?)?;
+
+    let mut table = Table::new(["Day", "Stars", "Solution", "Tests", "Status"]);
+    let (mut solved, mut half_solved, mut unsolved, mut missing) = (0, 0, 0, 0);
+    for day in 1..=EventDate::days_in_year(year) {
+        let date = EventDate { day, year };
+        if date.ensure_unlocked().is_err() {
+            break;
+        }
+        let stars = calendar.stars(day).unwrap_or(0);
+        let path = solution_path(&date);
+        let has_solution = path.exists();
+        // A solution that doesn't parse only spoils its own row
+        let tests =
+            has_solution.then(|| SolutionFile::read(path).and_then(|file| file.test_count()));
+
+        let status = match stars {
+            2 => {
+                solved += 1;
+                "solved"
+            }
+            1 => {
+                half_solved += 1;
+                "part 2 unsolved"
+            }
+            _ => {
+                unsolved += 1;
+                "unsolved"
+            }
+        };
+        let status = match &tests {
+            None if stars > 0 => {
+                missing += 1;
+                format!("{status}, no solution file")
+            }
+            Some(Ok(0)) => format!("{status}, no tests"),
+            Some(Err(err)) => format!("{status}, can't read solution: {err}"),
+            _ => status.to_string(),
+        };
+        let tests = match tests {
+            None => "-".to_string(),
+            Some(Ok(tests)) => tests.to_string(),
+            Some(Err(_)) => "error".to_string(),
+        };
+        table.push_row([
+            day.to_string(),
+            "*".repeat(stars.into()),
+            if has_solution { "yes" } else { "-" }.to_string(),
+            tests,
+            status,
+        ]);
+    }
+
+    println!("{year}: {} stars\n", calendar.total());
+    print!("{table}");
+    println!(
+        "\nSolved {solved}, half-solved {half_solved}, unsolved {unsolved}, {missing} days with stars but no solution file"
+    );
+    Ok(())
+}
@@ -1,10 +1,69 @@
-use crate::{aoc_client, event_date::EventDate, problem, solution};
+use std::io::Write;
+
+use super::{
+    countdown::countdown,
+    readme::write_readme,
+    testgen::{ExampleSelection, generate_tests},
+};
+use crate::{
+    aoc_client,
+    event_date::EventDate,
+    problem::Problem,
+    registry,
+    render::Ansi,
+    solution::{self, Part, readme_path, solution_path},
+    submission::{Cooldown, Hint, SubmissionHistory, SubmissionVerdict},
+};
 use anyhow::anyhow;
+use chrono::{Local, Utc};
+
+fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    print!("{prompt} [Y/n] ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    let read = std::io::stdin().read_line(&mut input)?;
+    Ok(read > 0 && matches!(input.trim(), "" | "y" | "Y"))
+}
+
+/// Prints the parts unlocked since `previous` was cached, straight after a
+/// correct answer, and offers to generate their tests.
+fn show_unlocked_parts(date: &EventDate, previous: &Problem) -> anyhow::Result<()> {
+    let refreshed = Problem::parse(&aoc_client::get_refreshed_problem(date)?);
+    let unlocked = refreshed
+        .unlocked_parts()
+        .into_iter()
+        .filter(|part| previous.article(*part).is_none())
+        .collect::<Vec<_>>();
+    if unlocked.is_empty() {
+        return Ok(());
+    }
+
+    print!("\n{}", Ansi::for_stdout().render(&refreshed, &unlocked));
+    if unlocked.contains(&Part::Two)
+        && solution_path(date).exists()
+        && confirm("\nGenerate tests for Part 2 now?")?
+    {
+        generate_tests(
+            Some(date.day),
+            Some(date.year),
+            &[Part::Two],
+            ExampleSelection::Prompt,
+            false,
+        )?;
+    }
+    Ok(())
+}
 
-pub fn submit(day: Option<u8>, year: Option<u16>, answer: Option<String>) -> anyhow::Result<()> {
+pub fn submit(
+    day: Option<u8>,
+    year: Option<u16>,
+    answer: Option<String>,
+    force: bool,
+    wait: bool,
+) -> anyhow::Result<SubmissionVerdict> {
     let date = EventDate::create_or_default(day, year);
-    let problem_html = aoc_client::get_problem(&date)?;
-    let part = if problem::part_two_unlocked(&problem_html) {
+    let problem = Problem::parse(&aoc_client::get_problem(&date)?);
+    let part = if problem.part_two_unlocked() {
         solution::Part::Two
     } else {
         solution::Part::One
@@ -13,8 +72,13 @@ pub fn submit(day: Option<u8>, year: Option<u16>, answer: Option<String>) -> any
         Some(answer) => Ok::<String, anyhow::Error>(answer),
         None => {
             let input = aoc_client::get_input(&date).unwrap();
-            solution::build(&date, true)?;
-            let result = solution::exec(&input, part, &date, true)?;
+            let result = match registry::find_linked(&date)? {
+                Some(registered) => registered.run(part, input),
+                None => {
+                    solution::build(&date, true)?;
+                    solution::exec(&input, part, &date, true)?
+                }
+            };
             match result {
                 solution::ExecResult::Complete { answer, .. } => Ok(answer),
                 solution::ExecResult::Failed(error) => {
@@ -23,12 +87,58 @@ pub fn submit(day: Option<u8>, year: Option<u16>, answer: Option<String>) -> any
             }
         }
     }?;
-    aoc_client::post_answer(&date, part.to_int(), answer)?;
-    println!(
-        "Submitted answer for Day {} Part {}",
-        date.day,
-        part.to_int(),
-    );
-    aoc_client::refresh_problem(&date)?;
-    Ok(())
+    let mut history = SubmissionHistory::load(&date)?;
+    if !force {
+        history
+            .check(part, &answer)
+            .map_err(|err| anyhow!("Refusing to submit: {err} (use --force to submit anyway)"))?;
+    }
+    let label = format!("Day {} Part {part}", date.day);
+    let verdict = loop {
+        if let Some(deadline) = Cooldown::deadline()? {
+            if !wait {
+                let remaining = (deadline - Utc::now()).to_std().unwrap_or_default();
+                eprintln!(
+                    "{label}: cooling down until {}, {}s left to wait (use --wait to resubmit automatically)",
+                    deadline.with_timezone(&Local).format("%H:%M:%S"),
+                    remaining.as_secs() + 1,
+                );
+                return Ok(SubmissionVerdict::RateLimited(remaining));
+            }
+            countdown(deadline, "Submitting in")?;
+        }
+        let verdict = aoc_client::post_answer(&date, part.to_int(), answer.clone())?;
+        history.record(part, &answer, &verdict);
+        history.save(&date)?;
+        if !(wait && matches!(verdict, SubmissionVerdict::RateLimited(_))) {
+            break verdict;
+        }
+    };
+    match &verdict {
+        SubmissionVerdict::Correct => {
+            println!("{label}: {answer} is the right answer!");
+            show_unlocked_parts(&date, &problem)?;
+            if readme_path(&date).exists() {
+                write_readme(&date)?;
+            }
+        }
+        SubmissionVerdict::Incorrect(hint) => {
+            let hint = match hint {
+                Some(Hint::TooHigh) => " (too high)",
+                Some(Hint::TooLow) => " (too low)",
+                None => "",
+            };
+            eprintln!("{label}: {answer} is not the right answer{hint}");
+        }
+        SubmissionVerdict::RateLimited(wait) => {
+            eprintln!("{label}: answer submitted too recently, {wait:?} left to wait");
+        }
+        SubmissionVerdict::AlreadyCompleted => {
+            eprintln!("{label}: already completed");
+        }
+        SubmissionVerdict::WrongLevel => {
+            eprintln!("{label}: not the part currently being solved");
+        }
+    }
+    Ok(verdict)
 }
@@ -1,12 +1,14 @@
-use std::{fmt::Display, fs, path::PathBuf};
+use std::fmt::Display;
 
 use itertools::Itertools as _;
 
 use crate::{
     aoc_client,
     event_date::EventDate,
-    problem::{self, CodeBlock},
+    example::{self, ExamplePair},
+    problem::{CodeBlock, Problem},
     solution::{Part, solution_path},
+    test_file::{ExampleTest, SolutionFile},
 };
 
 fn indent(text: &str, spaces: usize) -> String {
@@ -18,10 +20,7 @@ fn indent(text: &str, spaces: usize) -> String {
 }
 
 fn example_input_heuristic(code_block: &CodeBlock) -> u32 {
-    let CodeBlock {
-        content,
-        emphasized: _,
-    } = code_block;
+    let CodeBlock { content, .. } = code_block;
 
     let mut score = 0;
 
@@ -37,6 +36,7 @@ fn example_solution_heuristic(code_block: &CodeBlock) -> u32 {
     let CodeBlock {
         content,
         emphasized,
+        ..
     } = code_block;
 
     let mut score: u32 = 0;
@@ -95,13 +95,120 @@ fn prompt_select<'a, T: Display>(
     unreachable!();
 }
 
-pub fn generate_tests(day: Option<u8>, year: Option<u16>, parts: &[Part]) -> anyhow::Result<()> {
+/// Offers the proposed pairs, most likely first, so accepting the suggested
+/// ones is a single keypress. An empty selection means the user wants to pick
+/// the input and answer separately.
+fn prompt_pairs<'a>(
+    part: &Part,
+    pairs: &'a [ExamplePair],
+    page_size: usize,
+) -> anyhow::Result<Vec<&'a ExamplePair>> {
+    if pairs.is_empty() {
+        println!("No example input and answer pairs found for Part {part}");
+        return Ok(Vec::new());
+    }
+    let shown = &pairs[..pairs.len().min(page_size)];
+    for (i, pair) in shown.iter().enumerate() {
+        println!("{}:\n{}", i + 1, indent(&pair.to_string(), 4));
+    }
+    let suggested = example::distinct_pairs(shown);
+    let suggested_numbers = suggested
+        .iter()
+        .map(|pair| {
+            let position = shown.iter().position(|shown| shown == *pair).unwrap();
+            (position + 1).to_string()
+        })
+        .join(",");
+    print!(
+        "\nSelect examples for Part {part}, e.g. `1` or `1,2` (Enter for {suggested_numbers}, m to choose the input and answer separately): "
+    );
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    match input.trim() {
+        "" => Ok(suggested),
+        "m" => Ok(Vec::new()),
+        options => options
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|option| !option.is_empty())
+            .map(|option| match option.parse::<usize>() {
+                Ok(option) if option >= 1 && option <= shown.len() => Ok(&shown[option - 1]),
+                _ => Err(anyhow::anyhow!("Invalid option {option}")),
+            })
+            .collect(),
+    }
+}
+
+/// How testgen picks the example input and answer for each part.
+#[derive(Debug, Clone, Copy)]
+pub enum ExampleSelection {
+    /// Ask, offering the proposed pairs first
+    Prompt,
+    /// Take the most likely proposed pair without asking
+    Auto,
+    /// Take the input and answer at these 1-based positions in the part's
+    /// ranked candidates
+    Indices { input: usize, answer: usize },
+}
+
+/// The distinct inputs and answers of `pairs`, each ranked by its most likely
+/// pair. These are what --input-index and --answer-index count into.
+fn ranked_candidates(pairs: &[ExamplePair]) -> (Vec<&CodeBlock>, Vec<&CodeBlock>) {
+    let inputs = pairs
+        .iter()
+        .map(|pair| &pair.input)
+        .unique_by(|input| &input.content)
+        .collect();
+    let answers = pairs
+        .iter()
+        .map(|pair| &pair.answer)
+        .unique_by(|answer| &answer.content)
+        .collect();
+    (inputs, answers)
+}
+
+fn print_candidates(kind: &str, part: &Part, candidates: &[&CodeBlock]) {
+    println!("Example {kind}s for Part {part}:");
+    for (i, candidate) in candidates.iter().enumerate() {
+        println!("{}:\n{}", i + 1, indent(&candidate.content, 4));
+    }
+}
+
+fn select_index<'a>(
+    kind: &str,
+    options: &[&'a CodeBlock],
+    index: usize,
+) -> anyhow::Result<&'a CodeBlock> {
+    index
+        .checked_sub(1)
+        .and_then(|index| options.get(index).copied())
+        .ok_or(anyhow::anyhow!(
+            "No example {kind} {index}, there are only {}",
+            options.len()
+        ))
+}
+
+pub fn generate_tests(
+    day: Option<u8>,
+    year: Option<u16>,
+    parts: &[Part],
+    selection: ExampleSelection,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    // Each part ranks its own candidates, so indices only pick out an example
+    // for a part that's named explicitly
+    if matches!(selection, ExampleSelection::Indices { .. }) && parts.len() != 1 {
+        return Err(anyhow::anyhow!(
+            "--input-index and --answer-index select a single example, pick its part with -p"
+        ));
+    }
     let date = EventDate::create_or_default(day, year);
-    let problem = aoc_client::get_problem(&date)?;
-    let code_blocks = problem::parse_code_blocks(&problem);
+    let problem = Problem::parse(&aoc_client::get_problem(&date)?);
+    let code_blocks = problem.code_blocks();
     let page_size = 5;
-    let previous_answers = problem::previous_answers(&problem);
-    let unlocked_parts = problem::unlocked_parts(&problem);
+    let unlocked_parts = problem.unlocked_parts();
+    let previous_answers = problem.answers.clone();
     let parts = if parts.is_empty() {
         &unlocked_parts
     } else {
@@ -114,7 +221,7 @@ pub fn generate_tests(day: Option<u8>, year: Option<u16>, parts: &[Part]) -> any
         .filter(|cb| !previous_answers.contains(&cb.content))
         .sorted_by_key(example_input_heuristic)
         .rev()
-        .dedup()
+        .dedup_by(|a, b| a.content == b.content)
         .collect::<Vec<_>>();
 
     let example_solutions = code_blocks
@@ -122,100 +229,96 @@ pub fn generate_tests(day: Option<u8>, year: Option<u16>, parts: &[Part]) -> any
         .filter(|cb| !previous_answers.contains(&cb.content))
         .sorted_by_key(example_solution_heuristic)
         .rev()
-        .dedup()
+        .dedup_by(|a, b| a.content == b.content)
         .collect::<Vec<_>>();
 
+    // Dry runs leave the solution alone, it may not even exist yet
     let solution_file_path = solution_path(&date);
+    let mut solution_file = if dry_run {
+        SolutionFile::empty(solution_file_path.clone())
+    } else {
+        SolutionFile::read(solution_file_path.clone())?
+    };
 
     for part in parts {
-        let input_selection = prompt_select(
-            &format!("Select an input to use for Part {part}"),
-            &example_inputs,
-            page_size,
-        )?;
-
-        let solution_selection = prompt_select(
-            &format!("Select an example solution to use for Part {part}"),
-            &example_solutions,
-            page_size,
-        )?;
-
-        append_test_case(
-            &solution_file_path,
-            part,
-            &input_selection.content,
-            &solution_selection.content,
-        )?;
-
-        println!(
-            "Generated test for Part {part}:\nInput:\n{input}\nExpected Output:\n{expected_output}\n",
-            part = part,
-            input = input_selection.content,
-            expected_output = solution_selection.content
-        );
+        let pairs = example::propose_pairs(&problem, *part);
+        let (candidate_inputs, candidate_answers) = ranked_candidates(&pairs);
+        if dry_run {
+            print_candidates("input", part, &candidate_inputs);
+            print_candidates("answer", part, &candidate_answers);
+        }
+        let selected_pairs = match selection {
+            ExampleSelection::Prompt => prompt_pairs(part, &pairs, page_size)?,
+            ExampleSelection::Auto if pairs.is_empty() => {
+                return Err(anyhow::anyhow!(
+                    "No example input and answer pair found for Part {part}, use --input-index and --answer-index"
+                ));
+            }
+            ExampleSelection::Auto => example::distinct_pairs(&pairs),
+            ExampleSelection::Indices { .. } => Vec::new(),
+        };
+        let examples = match selection {
+            _ if !selected_pairs.is_empty() => selected_pairs
+                .iter()
+                .map(|pair| (&pair.input.content, &pair.answer.content))
+                .collect(),
+            ExampleSelection::Indices { input, answer } => vec![(
+                &select_index("input", &candidate_inputs, input)?.content,
+                &select_index("answer", &candidate_answers, answer)?.content,
+            )],
+            _ => {
+                let input_selection = prompt_select(
+                    &format!("Select an input to use for Part {part}"),
+                    &example_inputs,
+                    page_size,
+                )?;
+                let solution_selection = prompt_select(
+                    &format!("Select an example solution to use for Part {part}"),
+                    &example_solutions,
+                    page_size,
+                )?;
+                vec![(&input_selection.content, &solution_selection.content)]
+            }
+        };
+
+        for (input, expected_output) in examples {
+            let existing = if dry_run {
+                None
+            } else {
+                solution_file.tests()?.into_iter().find(|test| {
+                    test.part == part.to_int()
+                        && test.input == *input
+                        && test.expected_output == *expected_output
+                })
+            };
+            if let Some(test) = existing {
+                println!(
+                    "Part {part} already has a test for this example ({}), skipping",
+                    test.name
+                );
+                continue;
+            }
+            let test = ExampleTest {
+                name: solution_file.test_name(*part)?,
+                part: part.to_int(),
+                input: input.clone(),
+                expected_output: expected_output.clone(),
+            };
+            // Dry runs still insert into the in-memory file so later tests get unique names
+            solution_file.insert_test(&test)?;
+            if dry_run {
+                println!("{}", test.to_source());
+            } else {
+                println!(
+                    "Generated test for Part {part}:\nInput:\n{input}\nExpected Output:\n{expected_output}\n"
+                );
+            }
+        }
     }
 
-    println!("Tests appended to {solution_file_path:?}");
-    Ok(())
-}
-
-fn ensure_test_module(file_path: &PathBuf) -> anyhow::Result<()> {
-    let content = fs::read_to_string(file_path)?;
-
-    if content.contains("#[cfg(test)]") {
-        return Ok(());
+    if !dry_run {
+        solution_file.write()?;
+        println!("Tests appended to {solution_file_path:?}");
     }
-
-    let test_module = r#"
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-}
-"#;
-
-    fs::write(file_path, content + test_module)?;
     Ok(())
 }
-
-fn append_test_case(
-    file_path: &PathBuf,
-    part: &Part,
-    input: &str,
-    expected_output: &str,
-) -> anyhow::Result<()> {
-    ensure_test_module(file_path)?;
-
-    let content = fs::read_to_string(file_path)?;
-    let test_case = write_test_case(part, input, expected_output);
-
-    let test_module_end = content
-        .rfind('}')
-        .ok_or_else(|| anyhow::anyhow!("Could not find closing brace of test module"))?;
-
-    let new_content = format!(
-        "{}\n{}\n{}",
-        &content[..test_module_end],
-        test_case,
-        &content[test_module_end..]
-    );
-
-    fs::write(file_path, new_content)?;
-    Ok(())
-}
-
-fn write_test_case(part: &Part, input: &str, expected_output: &str) -> String {
-    format!(
-        r##"
-    #[test]
-    fn test_part{part_num}() {{
-        let input = r#"{input}"#.to_string();
-        let expected_output = r#"{expected_output}"#.to_string();
-        let result = part{part_num}(input).unwrap();
-        assert_eq!(result, expected_output);
-    }}"##,
-        part_num = part.to_int(),
-        input = input,
-        expected_output = expected_output
-    )
-}
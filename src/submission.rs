@@ -0,0 +1,245 @@
+use std::{fs, path::PathBuf, process::ExitCode, time::Duration};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use crate::{
+    cache::{self, EntryKind},
+    event_date::EventDate,
+    profile::Profile,
+    solution::Part,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// AoC's response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum SubmissionVerdict {
+    Correct,
+    Incorrect(Option<Hint>),
+    RateLimited(Duration),
+    AlreadyCompleted,
+    WrongLevel,
+}
+
+impl SubmissionVerdict {
+    /// Parses the answer page returned by AoC. `WrongLevel` is reported for both
+    /// already completed and still locked parts, AoC doesn't distinguish them.
+    pub fn parse(response: &str) -> anyhow::Result<Self> {
+        if response.contains("That's the right answer") {
+            return Ok(SubmissionVerdict::Correct);
+        }
+        if response.contains("That's not the right answer") {
+            let hint = if response.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if response.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            return Ok(SubmissionVerdict::Incorrect(hint));
+        }
+        if response.contains("You gave an answer too recently") {
+            let wait_re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")?;
+            let caps = wait_re
+                .captures(response)
+                .ok_or(anyhow!("Rate limited without a remaining wait time"))?;
+            let minutes: u64 = caps.get(1).map_or(Ok(0), |m| m.as_str().parse())?;
+            let seconds: u64 = caps[2].parse()?;
+            return Ok(SubmissionVerdict::RateLimited(Duration::from_secs(
+                minutes * 60 + seconds,
+            )));
+        }
+        if response.contains("You don't seem to be solving the right level") {
+            return Ok(SubmissionVerdict::WrongLevel);
+        }
+
+        let message = Regex::new(r"<main>(?s:(?P<main>.*))</main>")?
+            .captures(response)
+            .map(|captures| captures["main"].to_string())
+            .unwrap_or_else(|| format!("Full response: {response}"));
+        Err(anyhow!("Unrecognized submission response: {message}"))
+    }
+
+    /// How long AoC makes the whole account wait before its next answer, after
+    /// giving this verdict in `response`. Wrong answers cost a minute, or more
+    /// as they add up, which the response spells out.
+    pub fn cooldown(&self, response: &str) -> anyhow::Result<Option<Duration>> {
+        match self {
+            SubmissionVerdict::RateLimited(wait) => Ok(Some(*wait)),
+            SubmissionVerdict::Incorrect(_) => {
+                let wait_re =
+                    Regex::new(r"[Pp]lease wait (one|\d+) (second|minute)s? before trying again")?;
+                let Some(caps) = wait_re.captures(response) else {
+                    return Ok(Some(Duration::from_secs(60)));
+                };
+                let count: u64 = match &caps[1] {
+                    "one" => 1,
+                    count => count.parse()?,
+                };
+                let unit = if &caps[2] == "minute" { 60 } else { 1 };
+                Ok(Some(Duration::from_secs(count * unit)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// A distinct exit code per verdict so scripts can tell them apart, `1` is
+    /// left for general errors.
+    pub fn exit_code(&self) -> ExitCode {
+        let code = match self {
+            SubmissionVerdict::Correct => 0,
+            SubmissionVerdict::Incorrect(_) => 2,
+            SubmissionVerdict::RateLimited(_) => 3,
+            SubmissionVerdict::AlreadyCompleted => 4,
+            SubmissionVerdict::WrongLevel => 5,
+        };
+        ExitCode::from(code)
+    }
+}
+
+#[derive(Debug, Clone, bincode::Encode, bincode::Decode)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: SubmissionVerdict,
+    /// Unix timestamp of when the answer was submitted
+    pub submitted_at: i64,
+}
+
+/// Every answer submitted for a day, stored alongside the day's cached problem and input.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct SubmissionHistory {
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionHistory {
+    fn path(date: &EventDate) -> anyhow::Result<PathBuf> {
+        let cache_dir = Profile::selected().cache_dir()?;
+        Ok(EntryKind::Submissions.path(&cache_dir, date))
+    }
+
+    pub fn load(date: &EventDate) -> anyhow::Result<Self> {
+        let path = Self::path(date)?;
+        if !path.exists() {
+            return Ok(SubmissionHistory::default());
+        }
+        let bytes = fs::read(path)?;
+        let (history, _) = bincode::decode_from_slice(&bytes, bincode::config::standard())?;
+        Ok(history)
+    }
+
+    pub fn save(&self, date: &EventDate) -> anyhow::Result<()> {
+        let path = Self::path(date)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        cache::write_atomically(
+            &path,
+            bincode::encode_to_vec(self, bincode::config::standard())?,
+        )?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: &SubmissionVerdict) {
+        self.submissions.push(Submission {
+            part: part.to_int(),
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+            submitted_at: Utc::now().timestamp(),
+        });
+    }
+
+    fn rejected(&self, part: Part) -> impl Iterator<Item = (&str, Option<Hint>)> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.part == part.to_int())
+            .filter_map(|submission| match submission.verdict {
+                SubmissionVerdict::Incorrect(hint) => Some((submission.answer.as_str(), hint)),
+                _ => None,
+            })
+    }
+
+    /// Errors if `answer` is already known to be wrong, either because it was
+    /// rejected before or because it falls outside the bounds implied by
+    /// earlier "too high"/"too low" verdicts.
+    pub fn check(&self, part: Part, answer: &str) -> anyhow::Result<()> {
+        if self.rejected(part).any(|(rejected, _)| rejected == answer) {
+            return Err(anyhow!("{answer} was already rejected for Part {part}"));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        for (rejected, hint) in self.rejected(part) {
+            let Ok(rejected_value) = rejected.parse::<i128>() else {
+                continue;
+            };
+            match hint {
+                Some(Hint::TooHigh) if value >= rejected_value => {
+                    return Err(anyhow!(
+                        "{answer} is at least {rejected}, which was already too high for Part {part}"
+                    ));
+                }
+                Some(Hint::TooLow) if value <= rejected_value => {
+                    return Err(anyhow!(
+                        "{answer} is at most {rejected}, which was already too low for Part {part}"
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// When the selected profile may submit its next answer. AoC's cooldown covers
+/// the whole account, so it's kept once per profile rather than per day.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct Cooldown {
+    /// Unix timestamp of when the cooldown lifts
+    pub until: i64,
+}
+
+impl Cooldown {
+    fn path() -> anyhow::Result<PathBuf> {
+        Ok(Profile::selected().cache_dir()?.join("cooldown.bin"))
+    }
+
+    /// Starts a cooldown of `wait` from now, unless a longer one is running.
+    pub fn start(wait: Duration) -> anyhow::Result<()> {
+        let until = Utc::now() + wait;
+        if Self::deadline()?.is_some_and(|deadline| deadline >= until) {
+            return Ok(());
+        }
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let cooldown = Cooldown {
+            until: until.timestamp(),
+        };
+        cache::write_atomically(
+            &path,
+            bincode::encode_to_vec(cooldown, bincode::config::standard())?,
+        )?;
+        Ok(())
+    }
+
+    /// When the running cooldown lifts, if there is one.
+    pub fn deadline() -> anyhow::Result<Option<DateTime<Utc>>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(path)?;
+        let (cooldown, _): (Cooldown, _) =
+            bincode::decode_from_slice(&bytes, bincode::config::standard())?;
+        Ok(DateTime::from_timestamp(cooldown.until, 0).filter(|deadline| *deadline > Utc::now()))
+    }
+}
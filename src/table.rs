@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+/// A plain text table with columns padded to their widest cell.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<S: ToString>(headers: impl IntoIterator<Item = S>) -> Self {
+        Table {
+            headers: headers
+                .into_iter()
+                .map(|header| header.to_string())
+                .collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row<S: ToString>(&mut self, row: impl IntoIterator<Item = S>) {
+        self.rows
+            .push(row.into_iter().map(|cell| cell.to_string()).collect());
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths = self
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect::<Vec<_>>();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(i) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+        widths
+    }
+}
+
+fn write_row(
+    f: &mut std::fmt::Formatter<'_>,
+    cells: &[String],
+    widths: &[usize],
+) -> std::fmt::Result {
+    let line = widths
+        .iter()
+        .enumerate()
+        .map(|(i, width)| {
+            let cell = cells.get(i).map(String::as_str).unwrap_or("");
+            format!("{cell:<width$}")
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    writeln!(f, "{}", line.trim_end())
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths = self.column_widths();
+        write_row(f, &self.headers, &widths)?;
+        let separator = widths
+            .iter()
+            .map(|width| "─".repeat(*width))
+            .collect::<Vec<_>>();
+        write_row(f, &separator, &widths)?;
+        for row in &self.rows {
+            write_row(f, row, &widths)?;
+        }
+        Ok(())
+    }
+}
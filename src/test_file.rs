@@ -0,0 +1,253 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::anyhow;
+use proc_macro2::LineColumn;
+use syn::{Expr, Item, ItemFn, ItemMod, Lit, Pat, Stmt};
+
+use crate::solution::Part;
+
+/// An example test in a solution's test module, as written by
+/// [`SolutionFile::insert_test`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleTest {
+    pub name: String,
+    pub part: u8,
+    pub input: String,
+    pub expected_output: String,
+}
+
+impl ExampleTest {
+    /// The test function, indented to sit inside the test module.
+    pub fn to_source(&self) -> String {
+        format!(
+            r#"    #[test]
+    fn {name}() {{
+        let input = {input}.to_string();
+        let expected_output = {expected_output}.to_string();
+        let result = part{part}(input).unwrap();
+        assert_eq!(result, expected_output);
+    }}
+"#,
+            name = self.name,
+            input = raw_string(&self.input),
+            expected_output = raw_string(&self.expected_output),
+            part = self.part,
+        )
+    }
+}
+
+/// A raw string literal for `content` with enough `#`s that nothing in the
+/// content can end it early.
+pub fn raw_string(content: &str) -> String {
+    let hashes = (1..)
+        .map(|count| "#".repeat(count))
+        .find(|hashes| !content.contains(&format!("\"{hashes}")))
+        .unwrap();
+    format!("r{hashes}\"{content}\"{hashes}")
+}
+
+/// Converts a span location to a byte offset into `content`.
+fn offset(content: &str, location: LineColumn) -> usize {
+    let line_start = content
+        .split_inclusive('\n')
+        .take(location.line - 1)
+        .map(str::len)
+        .sum::<usize>();
+    let line = &content[line_start..];
+    line_start
+        + line
+            .char_indices()
+            .nth(location.column)
+            .map_or(line.len(), |(i, _)| i)
+}
+
+fn is_cfg_test(module: &ItemMod) -> bool {
+    module.attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        Expr::MethodCall(call) => string_literal(&call.receiver),
+        _ => None,
+    }
+}
+
+/// The part called by `expr`, e.g. `2` for `part2(input).unwrap()`.
+fn called_part(expr: &Expr) -> Option<u8> {
+    match expr {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(path) => path
+                .path
+                .get_ident()?
+                .to_string()
+                .strip_prefix("part")?
+                .parse()
+                .ok(),
+            _ => None,
+        },
+        Expr::MethodCall(call) => called_part(&call.receiver),
+        _ => None,
+    }
+}
+
+fn example_test(function: &ItemFn) -> Option<ExampleTest> {
+    if !function
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("test"))
+    {
+        return None;
+    }
+    let mut input = None;
+    let mut expected_output = None;
+    let mut part = None;
+    for stmt in &function.block.stmts {
+        let Stmt::Local(local) = stmt else {
+            continue;
+        };
+        let (Pat::Ident(pat), Some(init)) = (&local.pat, &local.init) else {
+            continue;
+        };
+        match pat.ident.to_string().as_str() {
+            "input" => input = string_literal(&init.expr),
+            "expected_output" => expected_output = string_literal(&init.expr),
+            _ => part = part.or(called_part(&init.expr)),
+        }
+    }
+    Some(ExampleTest {
+        name: function.sig.ident.to_string(),
+        part: part?,
+        input: input?,
+        expected_output: expected_output?,
+    })
+}
+
+/// A solution's source, edited in place so its formatting is kept.
+pub struct SolutionFile {
+    path: PathBuf,
+    content: String,
+}
+
+impl SolutionFile {
+    pub fn read(path: PathBuf) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(&path)?;
+        Ok(SolutionFile { path, content })
+    }
+
+    /// A solution without any content, for laying out tests without reading
+    /// the file at `path`.
+    pub fn empty(path: PathBuf) -> Self {
+        SolutionFile {
+            path,
+            content: String::new(),
+        }
+    }
+
+    pub fn write(&self) -> anyhow::Result<()> {
+        fs::write(&self.path, &self.content)?;
+        Ok(())
+    }
+
+    fn parse(&self) -> anyhow::Result<syn::File> {
+        syn::parse_file(&self.content)
+            .map_err(|err| anyhow!("Failed to parse {}: {err}", self.path.display()))
+    }
+
+    fn test_module(file: &syn::File) -> Option<&ItemMod> {
+        file.items.iter().find_map(|item| match item {
+            Item::Mod(module) if is_cfg_test(module) && module.content.is_some() => Some(module),
+            _ => None,
+        })
+    }
+
+    fn test_functions(&self) -> anyhow::Result<Vec<ItemFn>> {
+        let file = self.parse()?;
+        let Some((_, items)) = Self::test_module(&file).and_then(|module| module.content.as_ref())
+        else {
+            return Ok(Vec::new());
+        };
+        Ok(items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(function) => Some(function.clone()),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// The number of `#[test]` functions in the `#[cfg(test)]` module.
+    pub fn test_count(&self) -> anyhow::Result<usize> {
+        Ok(self
+            .test_functions()?
+            .iter()
+            .filter(|function| {
+                function
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("test"))
+            })
+            .count())
+    }
+
+    /// The example tests in the `#[cfg(test)]` module.
+    pub fn tests(&self) -> anyhow::Result<Vec<ExampleTest>> {
+        Ok(self
+            .test_functions()?
+            .iter()
+            .filter_map(example_test)
+            .collect())
+    }
+
+    /// `test_part1` for the first test of Part One, then `test_part1_2` and so
+    /// on, skipping names of any function already in the test module.
+    pub fn test_name(&self, part: Part) -> anyhow::Result<String> {
+        let functions = self.test_functions()?;
+        let base = format!("test_part{}", part.to_int());
+        let taken = |name: &str| functions.iter().any(|function| function.sig.ident == name);
+        if !taken(&base) {
+            return Ok(base);
+        }
+        Ok((2..)
+            .map(|i| format!("{base}_{i}"))
+            .find(|name| !taken(name))
+            .unwrap())
+    }
+
+    /// Adds `test` at the end of the `#[cfg(test)]` module, creating the
+    /// module at the end of the file if there isn't one.
+    pub fn insert_test(&mut self, test: &ExampleTest) -> anyhow::Result<()> {
+        let file = self.parse()?;
+        let Some(module) = Self::test_module(&file) else {
+            if !self.content.ends_with('\n') {
+                self.content.push('\n');
+            }
+            self.content
+                .push_str("\n#[cfg(test)]\nmod tests {\n    use super::*;\n}\n");
+            return self.insert_test(test);
+        };
+
+        let (brace, items) = module.content.as_ref().unwrap();
+        let close = offset(&self.content, brace.span.close().start());
+        let line_start = self.content[..close].rfind('\n').map_or(0, |i| i + 1);
+        let mut source = test.to_source();
+        if !items.is_empty() {
+            source.insert(0, '\n');
+        }
+        if self.content[line_start..close].trim().is_empty() {
+            self.content.insert_str(line_start, &source);
+        } else {
+            source.insert(0, '\n');
+            self.content.insert_str(close, &source);
+        }
+        Ok(())
+    }
+}
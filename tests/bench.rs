@@ -0,0 +1,181 @@
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use aor::{
+    bench::{self, Budget, Comparison, Phase, Stats},
+    bench_history::{self, Baseline, BenchHistory, BenchRecord},
+    event_date::EventDate,
+    protocol::{self, Frame},
+    solution::Part,
+};
+
+fn stats(runs: usize, mean_ns: u64, stddev_ns: u64) -> Stats {
+    Stats {
+        runs,
+        min: Duration::from_nanos(mean_ns - stddev_ns),
+        median: Duration::from_nanos(mean_ns),
+        mean: Duration::from_nanos(mean_ns),
+        stddev: Duration::from_nanos(stddev_ns),
+        p95: Duration::from_nanos(mean_ns + 2 * stddev_ns),
+    }
+}
+
+#[test]
+fn parses_durations_with_units() {
+    assert_eq!(bench::parse_duration("5s").unwrap(), Duration::from_secs(5));
+    assert_eq!(
+        bench::parse_duration("1.5s").unwrap(),
+        Duration::from_millis(1500)
+    );
+    assert_eq!(
+        bench::parse_duration("250us").unwrap(),
+        Duration::from_micros(250)
+    );
+    assert_eq!(
+        bench::parse_duration("2m").unwrap(),
+        Duration::from_secs(120)
+    );
+    assert!(bench::parse_duration("5").is_err());
+    assert!(bench::parse_duration("5h").is_err());
+    assert!(bench::parse_duration("99999999999999999999s").is_err());
+}
+
+#[test]
+fn summarizes_samples() {
+    let samples = (1..=20).map(Duration::from_micros).collect::<Vec<_>>();
+    let stats = Stats::new(&samples).unwrap();
+    assert_eq!(stats.runs, 20);
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.median, Duration::from_micros(10));
+    assert_eq!(stats.p95, Duration::from_micros(19));
+    assert_eq!(stats.mean, Duration::from_nanos(10_500));
+    assert_eq!(stats.stddev.as_nanos(), 5916);
+    assert!(Stats::new(&[]).is_none());
+}
+
+#[test]
+fn measures_after_warming_up() {
+    let mut calls = 0;
+    let samples = bench::measure(
+        Budget::Iterations(50),
+        || (),
+        |()| {
+            calls += 1;
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(samples.len(), 50);
+    assert_eq!(calls, 55);
+
+    let failed = bench::measure(
+        Budget::Time(Duration::from_secs(1)),
+        || (),
+        |()| anyhow::bail!("Not implemented"),
+    );
+    assert_eq!(failed.unwrap_err().to_string(), "Not implemented");
+}
+
+#[test]
+fn solution_binaries_report_samples_per_phase() {
+    let report_path = std::env::temp_dir().join(format!("aor-bench-{}.report", std::process::id()));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_day_01_2025"))
+        .args(["--part", "2"])
+        .args(Budget::Iterations(20).to_args())
+        .env(protocol::REPORT_PATH_VAR, &report_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let frames = protocol::read_report(&report_path).unwrap();
+    let _ = fs::remove_file(&report_path);
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let phases = frames
+        .iter()
+        .map(|frame| match frame {
+            Frame::Samples { phase, samples } => (*phase, samples.len()),
+            frame => panic!("expected samples, got {frame:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(phases, [(Phase::Parse, 20), (Phase::Solve, 20)]);
+}
+
+#[test]
+fn compares_means_with_welchs_t_test() {
+    // t = 2 with 10 degrees of freedom
+    let comparison = Comparison::new(&stats(6, 11_155, 1000), &stats(6, 10_000, 1000));
+    assert!((comparison.p_value - 0.0734).abs() < 1e-3, "{comparison:?}");
+    assert!(!comparison.significant());
+    assert_eq!(comparison.to_string(), "no change (p = 0.073)");
+
+    let slower = Comparison::new(&stats(1000, 12_000, 500), &stats(1000, 10_000, 500));
+    assert!(slower.significant());
+    assert!((slower.slowdown_percent() - 20.0).abs() < 1e-9);
+    assert_eq!(slower.to_string(), "1.20x slower (p = 0.000)");
+
+    let faster = Comparison::new(&stats(1000, 5_000, 500), &stats(1000, 10_000, 500));
+    assert_eq!(faster.to_string(), "2.00x faster (p = 0.000)");
+}
+
+#[test]
+fn history_finds_the_baseline_to_compare_with() {
+    let date = EventDate { day: 1, year: 2025 };
+    let committed = bench_history::source_hash(b"fn part1() {}");
+    let edited = bench_history::source_hash(b"fn part1() { todo!() }");
+    let record = |part, commit: &str, source_hash: &str, mean_ns| {
+        BenchRecord::new(
+            &date,
+            part,
+            Phase::Solve,
+            Some(commit.to_string()),
+            source_hash.to_string(),
+            stats(100, mean_ns, 10),
+        )
+    };
+    let history = BenchHistory {
+        records: vec![
+            record(Part::One, "aaaaaaa", &committed, 1000),
+            record(Part::Two, "aaaaaaa", &committed, 5000),
+            record(Part::One, "aaaaaaa", &edited, 1500),
+            record(Part::One, "bbbbbbb", &committed, 2000),
+        ],
+    };
+    let current = record(Part::One, "ccccccc", &committed, 3000);
+    let previous = history
+        .baseline(&current, &Baseline::Previous, None)
+        .unwrap();
+    assert_eq!(previous.commit.as_deref(), Some("bbbbbbb"));
+    let at_commit = Baseline::Commit("aaa".to_string());
+    let latest = history.baseline(&current, &at_commit, None).unwrap();
+    assert_eq!(latest.stats.mean, Duration::from_nanos(1500));
+    let as_committed = history
+        .baseline(&current, &at_commit, Some(&committed))
+        .unwrap();
+    assert_eq!(as_committed.stats.mean, Duration::from_nanos(1000));
+    assert!(
+        history
+            .baseline(&current, &Baseline::Commit("ddd".to_string()), None)
+            .is_none()
+    );
+
+    let path = std::env::temp_dir().join(format!("aor-bench-history-{}.bin", std::process::id()));
+    history.save(&path).unwrap();
+    let loaded = BenchHistory::load(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(loaded.records, history.records);
+    assert_eq!(bench_history::source_hash(b""), "cbf29ce484222325");
+    assert_eq!(bench_history::source_hash(b"a"), "af63dc4c8601ec8c");
+}
@@ -0,0 +1,800 @@
+use std::{
+    fs,
+    io::Write,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    time::Duration,
+};
+
+use aor::mock_server::{MockAoc, MockServer};
+
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+/// A scratch checkout with its own cache, talking to a mock AoC server.
+struct Workspace {
+    root: PathBuf,
+    server: MockServer,
+}
+
+impl Workspace {
+    fn new(name: &str) -> Self {
+        Workspace::with_cooldown(name, Duration::from_secs(60))
+    }
+
+    fn with_cooldown(name: &str, cooldown: Duration) -> Self {
+        let root = std::env::temp_dir().join(format!("aor-e2e-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/solutions")).unwrap();
+        fs::copy(
+            Path::new(MANIFEST_DIR).join("src/template.rs"),
+            root.join("src/template.rs"),
+        )
+        .unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"e2e\"\n").unwrap();
+        let server = MockServer::start(
+            MockAoc::new(Path::new(MANIFEST_DIR).join("tests/fixtures/synthetic"))
+                .cooldown(cooldown),
+        )
+        .unwrap();
+        Workspace { root, server }
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.root.join("cache")
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aor"));
+        command
+            .args(args)
+            .current_dir(&self.root)
+            .env("AOC_URL", self.server.url())
+            .env("AOC_AUTH_TOKEN", "e2e")
+            .env("AOC_CACHE_DIR", self.cache_dir())
+            .env_remove("VISUAL");
+        command
+    }
+
+    fn aor(&self, args: &[&str], stdin: &str) -> Output {
+        let mut child = self
+            .command(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    /// A command for `args` with 2025 solutions declared for `days`, whose
+    /// binaries are copied from this crate's build where it has them. cargo
+    /// is stubbed out and logs each build to cargo.log.
+    fn prebuilt_command(&self, days: &[u8], args: &[&str]) -> Command {
+        let template = self.read("src/template.rs");
+        let prebuilt = Path::new(env!("CARGO_BIN_EXE_day_01_2025"))
+            .parent()
+            .unwrap();
+        let target = self.root.join("target/debug");
+        fs::create_dir_all(&target).unwrap();
+        let mut manifest = "[package]\nname = \"e2e\"\n".to_string();
+        for day in days {
+            let bin_name = format!("day_{day:02}_2025");
+            fs::write(
+                self.root.join(format!("src/solutions/{bin_name}.rs")),
+                &template,
+            )
+            .unwrap();
+            manifest.push_str(&format!("\n[[bin]]\nname = \"{bin_name}\"\n"));
+            if prebuilt.join(&bin_name).exists() {
+                fs::copy(prebuilt.join(&bin_name), target.join(&bin_name)).unwrap();
+            }
+        }
+        fs::write(self.root.join("Cargo.toml"), manifest).unwrap();
+
+        let bin = self.root.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(
+            bin.join("cargo"),
+            "#!/bin/sh\necho \"$@\" >> \"$(dirname \"$0\")/../cargo.log\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(bin.join("cargo"), fs::Permissions::from_mode(0o755)).unwrap();
+        let path = format!("{}:{}", bin.display(), std::env::var("PATH").unwrap());
+        let mut command = self.command(args);
+        command
+            .env("PATH", path)
+            .env("CARGO_MANIFEST_DIR", &self.root);
+        command
+    }
+
+    fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.root.join(path)).unwrap()
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// A problem page with `answers` accepted.
+fn solved_page(answers: &[&str]) -> String {
+    let answers = answers
+        .iter()
+        .map(|answer| format!("<p>Your puzzle answer was <code>{answer}</code>.</p>"))
+        .collect::<String>();
+    format!("<main><article><h2>--- Day ---</h2></article>{answers}</main>")
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "stdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn init_creates_solution_and_caches_input() {
+    let workspace = Workspace::new("init");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015"], ""));
+
+    assert!(
+        workspace
+            .read("src/solutions/day_01_2015.rs")
+            .contains("Day 1, 2015")
+    );
+    assert!(
+        workspace
+            .read("Cargo.toml")
+            .contains("name = \"day_01_2015\"")
+    );
+    assert_eq!(
+        fs::read_to_string(workspace.cache_dir().join("2015/day/1/input.txt")).unwrap(),
+        "100\n200\n300\n400"
+    );
+}
+
+#[test]
+fn submit_reports_correct_and_rejected_answers() {
+    let workspace = Workspace::new("submit");
+    assert_success(&workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "1000"], ""));
+
+    // Answers are form-encoded, so reserved characters reach AoC intact
+    assert_success(&workspace.aor(&["submit", "-d", "4", "-y", "2015", "-a", "a+b = c&d"], ""));
+
+    // Part Two is unlocked now, so the next answer is for it
+    let too_low = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "399"], "");
+    assert_eq!(too_low.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&too_low.stderr)
+            .contains("Day 1 Part 2: 399 is not the right answer (too low)")
+    );
+}
+
+#[test]
+fn testgen_appends_example_test() {
+    let workspace = Workspace::new("testgen");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015"], ""));
+    assert_success(&workspace.aor(&["testgen", "-d", "1", "-y", "2015"], "\n"));
+
+    let solution = workspace.read("src/solutions/day_01_2015.rs");
+    assert!(solution.contains("fn test_part1()"));
+    assert!(solution.contains("r#\"199\n200\n208\n210\"#"));
+    assert!(solution.contains("r#\"817\"#"));
+}
+
+#[test]
+fn submit_refuses_known_wrong_answers() {
+    let workspace = Workspace::with_cooldown("submit-history", Duration::ZERO);
+    let too_low = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "999"], "");
+    assert_eq!(too_low.status.code(), Some(2));
+
+    for answer in ["999", "500"] {
+        let refused = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", answer], "");
+        assert_eq!(refused.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&refused.stderr).contains("Refusing to submit"));
+    }
+
+    let forced = workspace.aor(
+        &["submit", "-d", "1", "-y", "2015", "-a", "999", "--force"],
+        "",
+    );
+    assert_eq!(forced.status.code(), Some(2));
+}
+
+#[test]
+fn submit_respects_cooldown() {
+    let workspace = Workspace::new("submit-cooldown");
+    let too_low = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "999"], "");
+    assert_eq!(too_low.status.code(), Some(2));
+
+    // The wrong answer's cooldown covers the whole account, so no day's answer
+    // is sent until it lifts
+    for day in ["1", "2"] {
+        let refused = workspace.aor(&["submit", "-d", day, "-y", "2015", "-a", "1000"], "");
+        assert_eq!(refused.status.code(), Some(3));
+        assert!(String::from_utf8_lossy(&refused.stderr).contains("--wait"));
+    }
+
+    // Without the local record AoC's own rate limit starts it again
+    let cooldown = workspace.cache_dir().join("cooldown.bin");
+    fs::remove_file(&cooldown).unwrap();
+    let rate_limited = workspace.aor(&["submit", "-d", "2", "-y", "2015", "-a", "1000"], "");
+    assert_eq!(rate_limited.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&rate_limited.stderr).contains("submitted too recently"));
+    assert!(cooldown.exists());
+}
+
+#[test]
+fn submit_waits_out_cooldown_and_resubmits() {
+    let workspace = Workspace::with_cooldown("submit-wait", Duration::from_secs(2));
+    let too_low = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "999"], "");
+    assert_eq!(too_low.status.code(), Some(2));
+
+    assert_success(&workspace.aor(
+        &["submit", "-d", "1", "-y", "2015", "-a", "1000", "--wait"],
+        "",
+    ));
+}
+
+#[test]
+fn leaderboard_renders_members_by_rank() {
+    let workspace = Workspace::new("leaderboard");
+    let output = workspace.aor(&["leaderboard", "--id", "1234", "-y", "2015"], "");
+    assert_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ada = stdout.find("Ada").unwrap();
+    let anonymous = stdout.find("(anonymous user #2)").unwrap();
+    assert!(ada < anonymous);
+    assert!(stdout.contains("15h10**"));
+    assert!(stdout.contains("20:00*"));
+    assert!(
+        workspace
+            .cache_dir()
+            .join("2015/leaderboard/private/view/1234.json")
+            .exists()
+    );
+}
+
+#[test]
+fn profiles_use_their_own_token_and_cache() {
+    let workspace = Workspace::new("profiles");
+    let missing_token = workspace.aor(
+        &["--profile", "alt", "init", "-d", "1", "-y", "2015", "-f"],
+        "",
+    );
+    assert!(!missing_token.status.success());
+
+    let mut command =
+        workspace.command(&["--profile", "alt", "init", "-d", "1", "-y", "2015", "-f"]);
+    assert_success(
+        &command
+            .env("AOC_AUTH_TOKEN_ALT", "e2e-alt")
+            .output()
+            .unwrap(),
+    );
+    assert!(
+        workspace
+            .cache_dir()
+            .join("profiles/alt/2015/day/1/input.txt")
+            .exists()
+    );
+    assert!(!workspace.cache_dir().join("2015/day/1/input.txt").exists());
+
+    let escaping = workspace.aor(
+        &["--profile", "../alt", "init", "-d", "1", "-y", "2015", "-f"],
+        "",
+    );
+    assert!(!escaping.status.success());
+    assert!(String::from_utf8_lossy(&escaping.stderr).contains("Invalid profile name"));
+    assert!(!workspace.cache_dir().join("alt").exists());
+
+    let no_profiles = workspace
+        .command(&["run", "-d", "1", "-y", "2015", "--all-profiles"])
+        .env_remove("AOC_AUTH_TOKEN")
+        .output()
+        .unwrap();
+    assert!(!no_profiles.status.success());
+    assert!(String::from_utf8_lossy(&no_profiles.stderr).contains("No profiles to run against"));
+}
+
+#[test]
+fn auth_check_reports_user_or_expired_token() {
+    let workspace = Workspace::new("auth");
+    let output = workspace.aor(&["auth", "check"], "");
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Logged in as mock user"));
+
+    let expired = workspace
+        .command(&["auth", "check"])
+        .env("AOC_AUTH_TOKEN", "expired")
+        .output()
+        .unwrap();
+    assert!(!expired.status.success());
+    assert!(String::from_utf8_lossy(&expired.stderr).contains("has expired"));
+
+    let missing = workspace
+        .command(&["auth", "check"])
+        .env_remove("AOC_AUTH_TOKEN")
+        .output()
+        .unwrap();
+    assert_eq!(missing.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("AOC_AUTH_TOKEN is not set"));
+}
+
+#[test]
+fn expired_token_pages_are_not_cached() {
+    let workspace = Workspace::new("auth-cache");
+    let commands: [&[&str]; 2] = [
+        &["init", "-d", "1", "-y", "2015", "-f"],
+        &["testgen", "-d", "1", "-y", "2015", "-p", "1"],
+    ];
+    for args in commands {
+        let output = workspace
+            .command(args)
+            .env("AOC_AUTH_TOKEN", "expired")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+    }
+    assert!(!workspace.cache_dir().join("2015").exists());
+}
+
+#[test]
+fn locked_puzzles_are_never_requested() {
+    let workspace = Workspace::new("locked");
+    let output = workspace.aor(&["init", "-d", "1", "-y", "2099", "-f"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("doesn't unlock until"));
+    assert!(!workspace.cache_dir().join("2099").exists());
+}
+
+#[test]
+fn inputs_starting_with_angle_brackets_are_cached() {
+    let workspace = Workspace::new("angle-input");
+    assert_success(&workspace.aor(&["init", "-d", "3", "-y", "2015", "-f"], ""));
+    assert_eq!(
+        fs::read_to_string(workspace.cache_dir().join("2015/day/3/input.txt")).unwrap(),
+        "<^>v<<^^>>vv<>^v^^<<"
+    );
+}
+
+#[test]
+fn fetch_downloads_uncached_days_of_a_year() {
+    let workspace = Workspace::new("fetch");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015", "-f"], ""));
+
+    let output = workspace.aor(&["fetch", "-y", "2015", "-d", "1..=2", "--delay", "0"], "");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("fetched 3, already cached 1, failed 0"));
+    for path in ["2015/day/1.html", "2015/day/2.html", "2015/day/2/input.txt"] {
+        assert!(workspace.cache_dir().join(path).exists());
+    }
+
+    let negative_delay = workspace.aor(&["fetch", "-y", "2015", "--delay=-1"], "");
+    assert!(!negative_delay.status.success());
+    assert!(String::from_utf8_lossy(&negative_delay.stderr).contains("not a valid delay"));
+}
+
+#[test]
+fn cache_dir_can_be_set_in_config_file() {
+    let workspace = Workspace::new("config");
+    let config_cache = workspace.root.join("configured-cache");
+    fs::write(
+        workspace.root.join("config"),
+        format!("# aor settings\ncache_dir = {}\n", config_cache.display()),
+    )
+    .unwrap();
+
+    let output = workspace
+        .command(&["fetch", "-y", "2015", "-d", "1", "--delay", "0"])
+        .env_remove("AOC_CACHE_DIR")
+        .env("AOC_CONFIG", workspace.root.join("config"))
+        .output()
+        .unwrap();
+    assert_success(&output);
+    assert!(config_cache.join("2015/day/1/input.txt").exists());
+    assert!(!workspace.cache_dir().exists());
+}
+
+#[test]
+fn cache_subcommands_list_verify_and_purge_entries() {
+    let workspace = Workspace::new("cache");
+    assert_success(&workspace.aor(&["fetch", "-y", "2015", "-d", "1..=2", "--delay", "0"], ""));
+
+    let list = workspace.aor(&["cache", "list", "-y", "2015"], "");
+    assert_success(&list);
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(stdout.contains("2015-12-01"));
+    assert!(stdout.contains("2015-12-02"));
+
+    // Inputs like 2015 day 3's can start with `<` without being HTML
+    assert_success(&workspace.aor(&["init", "-d", "3", "-y", "2015", "-f"], ""));
+    assert_success(&workspace.aor(&["cache", "verify"], ""));
+    fs::write(
+        workspace.cache_dir().join("2015/day/2/input.txt"),
+        "<!DOCTYPE html>\n<html><body>Please log in</body></html>",
+    )
+    .unwrap();
+    let verify = workspace.aor(&["cache", "verify"], "");
+    assert!(!verify.status.success());
+    assert!(String::from_utf8_lossy(&verify.stderr).contains("Day 2, 2015 input"));
+
+    assert_success(&workspace.aor(
+        &["cache", "purge", "-y", "2015", "-d", "2", "-k", "input"],
+        "",
+    ));
+    assert!(!workspace.cache_dir().join("2015/day/2/input.txt").exists());
+    assert!(workspace.cache_dir().join("2015/day/2.html").exists());
+    assert_success(&workspace.aor(&["cache", "verify"], ""));
+}
+
+#[test]
+fn show_renders_problem_as_text() {
+    let workspace = Workspace::new("show");
+    let output = workspace.aor(&["show", "-d", "3", "-y", "2015"], "");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("--- Day 3: Sounding Line ---\n"));
+    assert!(stdout.contains("\n    199\n    200\n"));
+    assert!(stdout.contains("  • 210 & 200 (decreased)"));
+    assert!(stdout.contains("--- Part Two ---"));
+    assert!(!stdout.contains('\x1b'));
+
+    let part_two = workspace.aor(&["show", "-d", "3", "-y", "2015", "-p", "2"], "");
+    assert_success(&part_two);
+    let stdout = String::from_utf8_lossy(&part_two.stdout);
+    assert!(stdout.starts_with("--- Part Two ---\n"));
+    assert!(stdout.contains("Your puzzle answer was 1543."));
+
+    let locked = workspace.aor(&["show", "-d", "1", "-y", "2015", "-p", "2"], "");
+    assert!(!locked.status.success());
+    assert!(String::from_utf8_lossy(&locked.stderr).contains("isn't unlocked yet"));
+}
+
+#[test]
+fn init_and_submit_write_markdown_readme() {
+    let workspace = Workspace::new("readme");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015", "-r"], ""));
+
+    let readme = workspace.read("src/solutions/day_01_2015.md");
+    assert!(readme.starts_with("# Day 1: Depth Gauge\n\n<https://adventofcode.com/2015/day/1>"));
+    assert!(readme.contains("## Part One"));
+    assert!(readme.contains("```text\n199\n200\n208\n210\n```"));
+    assert!(readme.contains("a total depth of **`817`**."));
+    assert!(readme.contains("**What is the total depth?**"));
+
+    fs::write(workspace.root.join("src/solutions/day_01_2015.md"), "stale").unwrap();
+    assert_success(&workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "1000"], ""));
+    assert!(
+        workspace
+            .read("src/solutions/day_01_2015.md")
+            .starts_with("# Day 1: Depth Gauge")
+    );
+}
+
+#[test]
+fn testgen_runs_without_prompting() {
+    let workspace = Workspace::new("testgen-auto");
+
+    // Dry runs number the candidates the indices select from, and don't need
+    // the solution to exist
+    let dry_run = workspace
+        .command(&["testgen", "-d", "1", "-y", "2015", "--auto", "--dry-run"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_success(&dry_run);
+    let stdout = String::from_utf8_lossy(&dry_run.stdout);
+    assert!(stdout.contains("Example inputs for Part 1:\n1:\n    199\n    200\n"));
+    assert!(stdout.contains("Example answers for Part 1:\n1:\n    817\n"));
+    assert!(stdout.contains("fn test_part1()"));
+    assert!(!workspace.root.join("src/solutions/day_01_2015.rs").exists());
+
+    let second = workspace
+        .command(&[
+            "testgen",
+            "-d",
+            "4",
+            "-y",
+            "2015",
+            "-p",
+            "1",
+            "--input-index",
+            "2",
+            "--answer-index",
+            "2",
+            "--dry-run",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_success(&second);
+    let stdout = String::from_utf8_lossy(&second.stdout);
+    assert!(stdout.contains("Example inputs for Part 1:\n1:\n    1122\n2:\n    1111\n"));
+    assert!(stdout.contains("Example answers for Part 1:\n1:\n    3\n2:\n    4\n"));
+    assert!(stdout.contains("let input = r#\"1111\"#"));
+    assert!(stdout.contains("let expected_output = r#\"4\"#"));
+
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015"], ""));
+    let template = workspace.read("src/solutions/day_01_2015.rs");
+
+    let indexed = [
+        "testgen",
+        "-d",
+        "1",
+        "-y",
+        "2015",
+        "--input-index",
+        "1",
+        "--answer-index",
+        "1",
+    ];
+    let without_part = workspace
+        .command(&indexed)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(!without_part.status.success());
+    assert!(String::from_utf8_lossy(&without_part.stderr).contains("pick its part with -p"));
+    assert_eq!(workspace.read("src/solutions/day_01_2015.rs"), template);
+
+    let indexed = workspace
+        .command(&[&indexed[..], &["-p", "1"]].concat())
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_success(&indexed);
+    let solution = workspace.read("src/solutions/day_01_2015.rs");
+    assert!(solution.contains("r#\"199\n200\n208\n210\"#"));
+    assert!(solution.contains("r#\"817\"#"));
+}
+
+#[test]
+fn testgen_generates_uniquely_named_tests_once() {
+    let workspace = Workspace::new("testgen-multiple");
+    assert_success(&workspace.aor(&["init", "-d", "4", "-y", "2015"], ""));
+    let testgen = ["testgen", "-d", "4", "-y", "2015", "--auto"];
+    assert_success(&workspace.aor(&testgen, ""));
+
+    let solution = workspace.read("src/solutions/day_04_2015.rs");
+    for (name, input, answer) in [
+        ("test_part1", "1122", "3"),
+        ("test_part1_2", "1111", "4"),
+        ("test_part1_3", "1234", "0"),
+    ] {
+        assert!(solution.contains(&format!(
+            "fn {name}() {{\n        let input = r#\"{input}\"#.to_string();\n        let expected_output = r#\"{answer}\"#"
+        )));
+    }
+
+    let rerun = workspace.aor(&testgen, "");
+    assert_success(&rerun);
+    assert!(String::from_utf8_lossy(&rerun.stdout).contains("already has a test"));
+    assert_eq!(workspace.read("src/solutions/day_04_2015.rs"), solution);
+}
+
+#[test]
+fn status_cross_references_stars_with_solutions() {
+    let workspace = Workspace::new("status");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015"], ""));
+    assert_success(&workspace.aor(&["testgen", "-d", "1", "-y", "2015", "--auto"], ""));
+
+    let output = workspace.aor(&["status", "-y", "2015"], "");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = |day: &str| {
+        stdout
+            .lines()
+            .find(|line| line.split_whitespace().next() == Some(day))
+            .unwrap()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert!(stdout.starts_with("2015: 3 stars"));
+    assert_eq!(row("1"), "1 ** yes 1 solved");
+    assert_eq!(row("2"), "2 * - - part 2 unsolved, no solution file");
+    assert_eq!(row("3"), "3 - - unsolved");
+    assert!(stdout.contains("Solved 1, half-solved 1, unsolved 23, 1 days with stars"));
+    assert!(workspace.cache_dir().join("2015.html").exists());
+
+    fs::write(
+        workspace.root.join("src/solutions/day_03_2015.rs"),
+        "fn main(",
+    )
+    .unwrap();
+    let output = workspace.aor(&["status", "-y", "2015"], "");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = stdout
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some("3"))
+        .unwrap();
+    assert!(
+        row.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .starts_with("3 yes error unsolved, can't read solution:"),
+        "{row}"
+    );
+
+    assert_success(&workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "1000"], ""));
+    assert!(!workspace.cache_dir().join("2015.html").exists());
+}
+
+#[test]
+fn correct_submission_shows_newly_unlocked_part() {
+    let workspace = Workspace::new("unlocked");
+    assert_success(&workspace.aor(&["init", "-d", "1", "-y", "2015"], ""));
+    let output = workspace.aor(&["submit", "-d", "1", "-y", "2015", "-a", "1000"], "y\n\n");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let part_two = stdout.find("--- Part Two ---").unwrap();
+    assert!(!stdout.contains("--- Day 1: Depth Gauge ---"));
+    assert!(stdout[part_two..].contains("Now the elves want the deepest reading instead."));
+    assert!(stdout.contains("Generate tests for Part 2 now?"));
+
+    let solution = workspace.read("src/solutions/day_01_2015.rs");
+    assert!(solution.contains("fn test_part2()"));
+    assert!(solution.contains("r#\"210\"#"));
+}
+
+#[test]
+fn run_all_summarizes_solutions_it_cannot_run() {
+    let workspace = Workspace::new("run-all");
+    let no_solutions = workspace.aor(&["run", "--all", "-y", "2015"], "");
+    assert!(!no_solutions.status.success());
+    assert!(String::from_utf8_lossy(&no_solutions.stderr).contains("No solutions for 2015"));
+
+    let template = workspace.read("src/template.rs");
+    fs::write(
+        workspace.root.join("src/solutions/day_03_2015.rs"),
+        &template,
+    )
+    .unwrap();
+    fs::write(
+        workspace.root.join("src/solutions/day_04_2015.rs"),
+        &template,
+    )
+    .unwrap();
+    assert_success(&workspace.aor(&["init", "-d", "4", "-y", "2015", "-f"], ""));
+
+    let output = workspace.aor(&["run", "--all", "-y", "2015"], "");
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("Day  Part  Answer  Time  Status"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("3    -     -       -     no cached input"));
+    assert!(stdout.contains("4    -     -       -     no binary in Cargo.toml"));
+    assert!(stdout.contains("Total time: 0.00ns"));
+}
+
+#[test]
+fn run_all_runs_solutions_and_checks_accepted_answers() {
+    let workspace = Workspace::new("run-all-solved");
+    let cache = workspace.cache_dir().join("2025/day");
+    for day in [1, 2, 12] {
+        fs::create_dir_all(cache.join(format!("{day}"))).unwrap();
+    }
+    fs::write(
+        cache.join("1/input.txt"),
+        "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82",
+    )
+    .unwrap();
+    fs::write(cache.join("2/input.txt"), "11-22,95-115").unwrap();
+    fs::write(cache.join("12/input.txt"), "").unwrap();
+    fs::write(cache.join("1.html"), solved_page(&["3", "6"])).unwrap();
+    fs::write(cache.join("2.html"), solved_page(&["1"])).unwrap();
+
+    let output = workspace
+        .prebuilt_command(&[1, 2, 12], &["run", "--all", "-y", "2025"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+
+    assert_eq!(
+        workspace.read("cargo.log"),
+        "build --quiet --bin day_01_2025 --bin day_02_2025 --bin day_12_2025\n"
+    );
+    let rows = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>();
+    let row = |prefix: &str| {
+        rows.iter()
+            .find(|row| row.starts_with(prefix))
+            .unwrap_or_else(|| panic!("no {prefix:?} row in\n{stdout}"))
+    };
+    assert!(row("1 1 3 ").ends_with(" correct"));
+    assert!(row("1 2 6 ").ends_with(" correct"));
+    assert!(row("2 1 ").ends_with(" wrong, expected 1"));
+    assert!(row("2 2 ").ends_with(" unverified"));
+    assert!(row("12 1 - - failed: ").contains("day_12_2025"));
+    assert!(row("12 2 - - failed: ").contains("day_12_2025"));
+    let total = row("Total time: ");
+    assert_ne!(total, "Total time: 0.00ns");
+}
+
+#[test]
+fn run_all_profiles_fails_when_a_profile_fails() {
+    let workspace = Workspace::new("run-all-profiles");
+    let example = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+    for cache in [
+        workspace.cache_dir(),
+        workspace.cache_dir().join("profiles/alt"),
+    ] {
+        let day = cache.join("2025/day");
+        fs::create_dir_all(day.join("1")).unwrap();
+        fs::write(day.join("1/input.txt"), example).unwrap();
+        fs::write(day.join("1.html"), solved_page(&["3", "6"])).unwrap();
+    }
+    let args = ["run", "-d", "1", "-y", "2025", "--all-profiles"];
+
+    let output = workspace
+        .prebuilt_command(&[1], &args)
+        .env("AOC_AUTH_TOKEN_ALT", "e2e")
+        .output()
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n6\n3\n6\n");
+
+    fs::write(
+        workspace
+            .cache_dir()
+            .join("profiles/alt/2025/day/1/input.txt"),
+        "not a rotation",
+    )
+    .unwrap();
+    let output = workspace
+        .prebuilt_command(&[1], &args)
+        .env("AOC_AUTH_TOKEN_ALT", "e2e")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn run_reports_solutions_that_exit_without_reading_input() {
+    let workspace = Workspace::new("run-unread-input");
+    let day = workspace.cache_dir().join("2025/day/3");
+    fs::create_dir_all(&day).unwrap();
+    // Bigger than a pipe's buffer, so writing it fails once the solution exits
+    fs::write(day.join("input.txt"), "9".repeat(1 << 20)).unwrap();
+    let mut command = workspace.prebuilt_command(&[3], &["run", "-d", "3", "-y", "2025"]);
+    let target = workspace.root.join("target/debug");
+    fs::write(target.join("day_03_2025"), "#!/bin/sh\nexit 3\n").unwrap();
+    fs::set_permissions(
+        target.join("day_03_2025"),
+        fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+
+    let output = command.output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("without reporting an answer"), "{stderr}");
+    let leftovers = fs::read_dir(&target)
+        .unwrap()
+        .filter(|entry| {
+            let name = entry.as_ref().unwrap().file_name();
+            name.to_string_lossy().ends_with(".report")
+        })
+        .count();
+    assert_eq!(leftovers, 0);
+}
@@ -0,0 +1,200 @@
+use std::fs;
+
+use aor::{
+    event_date::EventDate,
+    example::propose_pairs,
+    html,
+    problem::{Block, Inline, Problem},
+    render::{self, Ansi},
+    solution::Part,
+};
+
+/// A hand-written page laid out like AoC's, with its own titles and text. AoC
+/// asks that puzzle text isn't redistributed, so real pages aren't committed.
+fn fixture(day: u8) -> Problem {
+    let path = format!(
+        "{}/tests/fixtures/synthetic/2015/day/{day}.html",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    Problem::parse(&fs::read_to_string(path).unwrap())
+}
+
+#[test]
+fn parses_title_articles_and_answers() {
+    let problem = fixture(3);
+    assert_eq!(problem.title, "Sounding Line");
+    assert_eq!(problem.unlocked_parts(), [Part::One, Part::Two]);
+    assert_eq!(problem.articles[1].heading, "Part Two");
+    assert_eq!(problem.answers, ["1521", "1543"]);
+
+    let unsolved = fixture(1);
+    assert_eq!(unsolved.title, "Depth Gauge");
+    assert!(!unsolved.part_two_unlocked());
+    assert!(unsolved.answers.is_empty());
+}
+
+#[test]
+fn parses_blocks_and_inline_markup() {
+    let problem = fixture(3);
+    let part_one = problem.article(Part::One).unwrap();
+    let Block::Paragraph(intro) = &part_one.blocks[0] else {
+        panic!("expected a paragraph, got {:?}", part_one.blocks[0]);
+    };
+    assert!(intro.contains(&Inline::Link {
+        text: "sonar".to_string(),
+        href: "https://en.wikipedia.org/wiki/Sonar".to_string(),
+    }));
+    assert_eq!(
+        part_one.blocks[2],
+        Block::Code("199\n200\n208\n210\n200".to_string())
+    );
+    let Block::List(items) = &part_one.blocks[4] else {
+        panic!("expected a list, got {:?}", part_one.blocks[4]);
+    };
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[2][1], Inline::Text(" & ".to_string()));
+
+    assert_eq!(
+        part_one.emphasized(),
+        ["increases", "N/A", "increased", "3"]
+    );
+    assert_eq!(
+        problem.article(Part::Two).unwrap().emphasized(),
+        ["three-measurement sliding window", "2"]
+    );
+}
+
+#[test]
+fn code_blocks_keep_their_surrounding_sentence() {
+    let code_blocks = fixture(3).code_blocks();
+    let example = code_blocks.iter().find(|code| code.multiline()).unwrap();
+    assert_eq!(
+        example.context,
+        "For example, suppose you had the following report:"
+    );
+    assert_eq!(example.part, Part::One);
+
+    let answer = code_blocks.iter().find(|code| code.content == "3").unwrap();
+    assert!(answer.emphasized);
+    assert_eq!(
+        answer.context,
+        "In this example, there are 3 measurements that are larger than the previous one."
+    );
+
+    let comparison = code_blocks
+        .iter()
+        .find(|code| code.content == "x < y")
+        .unwrap();
+    assert!(!comparison.emphasized);
+    assert_eq!(comparison.part, Part::Two);
+}
+
+#[test]
+fn pairs_examples_with_their_answers() {
+    let pairs = propose_pairs(&fixture(1), Part::One);
+    assert_eq!(pairs[0].input.content, "199\n200\n208\n210");
+    assert_eq!(pairs[0].answer.content, "817");
+    assert_eq!(pairs[0].confidence, 1.0);
+
+    let part_two = propose_pairs(&fixture(3), Part::Two);
+    assert_eq!(part_two[0].input.content, "199\n200\n208\n210\n200");
+    assert_eq!(part_two[0].input.part, Part::One);
+    assert_eq!(part_two[0].answer.content, "2");
+    assert!(part_two[0].confidence > 0.9);
+}
+
+#[test]
+fn pairs_inline_examples_in_the_same_sentence() {
+    let problem = Problem::parse(
+        r#"<article class="day-desc"><h2>--- Day 4: Matching Digits ---</h2>
+<p>For example:</p>
+<ul>
+<li><code>1122</code> produces a sum of <code>3</code> because the first digit matches the second.</li>
+<li><code>1111</code> produces <code>4</code>.</li>
+</ul>
+</article>"#,
+    );
+    let pairs = propose_pairs(&problem, Part::One)
+        .into_iter()
+        .map(|pair| (pair.input.content, pair.answer.content))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        [
+            ("1122".to_string(), "3".to_string()),
+            ("1111".to_string(), "4".to_string())
+        ]
+    );
+}
+
+#[test]
+fn decodes_character_references() {
+    assert_eq!(html::unescape("a &lt;&lt; b &amp;&amp; c"), "a << b && c");
+    assert_eq!(
+        html::unescape("&mdash;&hellip;&nbsp;&eacute;"),
+        "\u{2014}\u{2026}\u{a0}\u{e9}"
+    );
+    assert_eq!(html::unescape("&#39;&#x27;&#X41;&apos;"), "''A'");
+    // Browsers read the C1 range as Windows-1252, and invalid code points as U+FFFD
+    assert_eq!(html::unescape("&#150;&#x80;"), "\u{2013}\u{20ac}");
+    assert_eq!(
+        html::unescape("&#0;&#xd800;&#99999999999;"),
+        "\u{fffd}".repeat(3)
+    );
+    // Legacy entities don't need their semicolon, others are left as written
+    assert_eq!(html::unescape("&copy2015 &amp &lt"), "\u{a9}2015 & <");
+    assert_eq!(
+        html::unescape("&bogus; & &hellip &;"),
+        "&bogus; & &hellip &;"
+    );
+    assert_eq!(html::unescape("&amp;lt;"), "&lt;");
+}
+
+#[test]
+fn decodes_entities_in_synthetic_puzzle_text() {
+    let problem = fixture(5);
+    assert_eq!(problem.title, "Wire Kit");
+    assert_eq!(problem.answers, ["46065", "14134"]);
+
+    let code_blocks = problem.code_blocks();
+    let example = code_blocks.iter().find(|code| code.multiline()).unwrap();
+    assert_eq!(
+        example.content,
+        "123 -> x\n456 -> y\nx AND y -> d\nx OR y -> e\nNOT x -> h"
+    );
+    assert!(code_blocks.iter().any(|code| code.content == "\"x\" << 2"));
+    let answer = code_blocks
+        .iter()
+        .find(|code| code.context.starts_with("In the elves"))
+        .unwrap();
+    assert!(
+        answer
+            .context
+            .starts_with("In the elves' toy's instructions")
+    );
+
+    let Block::Paragraph(intro) = &problem.article(Part::One).unwrap().blocks[0] else {
+        panic!("expected a paragraph");
+    };
+    assert!(
+        intro[2]
+            .text()
+            .ends_with("a 16-bit signal\u{a0}\u{2014} a number from ")
+    );
+
+    let date = EventDate { day: 5, year: 2015 };
+    let markdown = render::markdown(&problem, &date);
+    assert!(markdown.contains("`123 -> x`"));
+    assert!(markdown.contains("`h: 65412`\u{2026}"));
+    assert!(markdown.contains("wires \u{2013} including"));
+    assert!(markdown.contains("Your puzzle answer was `46065`."));
+
+    let ansi = Ansi {
+        color: false,
+        width: 80,
+    }
+    .render(&problem, &[Part::One, Part::Two]);
+    assert!(ansi.contains("    x AND y -> d"));
+    assert!(ansi.contains("\"x\" << 2 still apply\u{2026}"));
+    assert!(!ansi.contains("&gt;") && !ansi.contains("&#"));
+}
@@ -0,0 +1,135 @@
+use std::{
+    fs,
+    io::Write,
+    os::unix::process::ExitStatusExt,
+    process::{Command, ExitStatus, Stdio},
+    time::Duration,
+};
+
+use aor::{
+    protocol::{self, Frame},
+    solution::ExecResult,
+};
+
+/// Runs a solution binary the way `aor run` does, returning its report along
+/// with what it printed itself.
+fn run_solution(binary: &str, part: u8, input: &str) -> (ExitStatus, Vec<Frame>, String) {
+    let report_path = std::env::temp_dir().join(format!(
+        "aor-protocol-{}-{part}-{}.report",
+        binary.rsplit('/').next().unwrap(),
+        std::process::id()
+    ));
+    let mut child = Command::new(binary)
+        .args(["--part", &part.to_string()])
+        .env(protocol::REPORT_PATH_VAR, &report_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let frames = protocol::read_report(&report_path).unwrap();
+    let _ = fs::remove_file(&report_path);
+    (
+        output.status,
+        frames,
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    )
+}
+
+#[test]
+fn frames_round_trip() {
+    let frames = [
+        Frame::Metric {
+            name: "states".to_string(),
+            value: 1024.0,
+        },
+        Frame::Answer {
+            answer: "Duration: 1s, 2ns\n42".to_string(),
+            duration: Duration::from_micros(1500),
+        },
+    ];
+    let mut bytes = Vec::new();
+    for frame in &frames {
+        protocol::write_frame(&mut bytes, frame).unwrap();
+    }
+    assert_eq!(
+        protocol::read_frames(&mut bytes.as_slice()).unwrap(),
+        frames
+    );
+
+    bytes.pop();
+    let err = protocol::read_frames(&mut bytes.as_slice()).unwrap_err();
+    assert!(err.to_string().starts_with("Truncated frame"), "{err}");
+}
+
+#[test]
+fn solutions_report_answers_without_using_stdout() {
+    let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+    let (status, frames, stdout) = run_solution(env!("CARGO_BIN_EXE_day_01_2025"), 1, input);
+    assert!(status.success());
+    assert!(stdout.is_empty(), "{stdout}");
+    let [Frame::Answer { answer, .. }] = frames.as_slice() else {
+        panic!("expected a single answer, got {frames:?}");
+    };
+    assert_eq!(answer, "3");
+}
+
+#[test]
+fn solutions_report_errors() {
+    let (status, frames, _) = run_solution(env!("CARGO_BIN_EXE_template"), 1, "");
+    assert!(!status.success());
+    let [Frame::Error { message }] = frames.as_slice() else {
+        panic!("expected a single error, got {frames:?}");
+    };
+    assert!(message.starts_with("Not implemented"), "{message}");
+    let ExecResult::Failed(message) = ExecResult::from_frames(frames, status) else {
+        panic!("expected a failure");
+    };
+    assert!(message.starts_with("Not implemented"), "{message}");
+}
+
+#[test]
+fn exec_results_collect_metrics_and_panics() {
+    let success = ExitStatus::from_raw(0);
+    let metric = Frame::Metric {
+        name: "cache hits".to_string(),
+        value: 3.0,
+    };
+    let answer = Frame::Answer {
+        answer: "42".to_string(),
+        duration: Duration::from_millis(5),
+    };
+    let ExecResult::Complete {
+        answer, metrics, ..
+    } = ExecResult::from_frames(vec![metric.clone(), answer], success)
+    else {
+        panic!("expected a complete result");
+    };
+    assert_eq!(answer, "42");
+    assert_eq!(metrics, [("cache hits".to_string(), 3.0)]);
+
+    let panicked = ExecResult::from_frames(
+        vec![
+            metric,
+            Frame::Panic {
+                message: "index out of bounds at src/solutions/day_01_2025.rs:3:5".to_string(),
+            },
+        ],
+        ExitStatus::from_raw(134),
+    );
+    assert!(
+        matches!(panicked, ExecResult::Failed(message) if message.starts_with("Panicked: index out of bounds"))
+    );
+
+    let silent = ExecResult::from_frames(Vec::new(), success);
+    assert!(
+        matches!(silent, ExecResult::Failed(message) if message.contains("without reporting an answer"))
+    );
+}
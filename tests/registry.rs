@@ -0,0 +1,51 @@
+use aor::{
+    event_date::EventDate,
+    registry::{self, Solution},
+    solution::{ExecResult, Part},
+};
+
+fn part1(input: String) -> anyhow::Result<String> {
+    Ok(input.lines().count().to_string())
+}
+
+fn part2(input: String) -> anyhow::Result<String> {
+    if input.is_empty() {
+        anyhow::bail!("Empty input");
+    }
+    panic!("Part 2 isn't solved yet");
+}
+
+// Registered from a file not named after a day, so it never shows up
+aor::register_solution!(part1, part2);
+
+const SOLUTION: Solution = Solution {
+    file: "src/solutions/day_02_2015.rs",
+    part1,
+    part2,
+    parse: None,
+};
+
+#[test]
+fn solutions_are_dated_by_their_file_name() {
+    assert_eq!(SOLUTION.date(), Some(EventDate { day: 2, year: 2015 }));
+    assert!(registry::solutions().is_empty());
+    assert!(registry::find(&EventDate { day: 2, year: 2015 }).is_none());
+}
+
+#[test]
+fn registered_solutions_run_in_process() {
+    let ExecResult::Complete { answer, .. } = SOLUTION.run(Part::One, "a\nb\n".to_string()) else {
+        panic!("expected part 1 to complete");
+    };
+    assert_eq!(answer, "2");
+
+    let ExecResult::Failed(error) = SOLUTION.run(Part::Two, String::new()) else {
+        panic!("expected part 2 to fail");
+    };
+    assert!(error.starts_with("Empty input"), "{error}");
+
+    let ExecResult::Failed(panic) = SOLUTION.run(Part::Two, "a".to_string()) else {
+        panic!("expected part 2 to panic");
+    };
+    assert_eq!(panic, "Panicked: Part 2 isn't solved yet");
+}
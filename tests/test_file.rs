@@ -0,0 +1,73 @@
+use std::{fs, path::PathBuf};
+
+use aor::{
+    solution::Part,
+    test_file::{ExampleTest, SolutionFile},
+};
+
+fn solution(name: &str, content: &str) -> (PathBuf, SolutionFile) {
+    let path = std::env::temp_dir().join(format!("aor-test-file-{name}-{}.rs", std::process::id()));
+    fs::write(&path, content).unwrap();
+    let file = SolutionFile::read(path.clone()).unwrap();
+    (path, file)
+}
+
+fn example(name: &str, input: &str, expected_output: &str) -> ExampleTest {
+    ExampleTest {
+        name: name.to_string(),
+        part: 1,
+        input: input.to_string(),
+        expected_output: expected_output.to_string(),
+    }
+}
+
+const SOLUTION: &str = r#"fn part1(input: String) -> anyhow::Result<String> {
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+
+// Trailing comment that must survive
+fn helper() {}
+"#;
+
+#[test]
+fn inserts_into_test_module_keeping_what_follows() {
+    let (path, mut file) = solution("follow", SOLUTION);
+    file.insert_test(&example("test_part1", "1\n2", "3"))
+        .unwrap();
+    file.write().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(content.contains(
+        "    use super::*;\n\n    #[test]\n    fn test_part1() {\n        let input = r#\"1\n2\"#.to_string();"
+    ));
+    assert!(
+        content.ends_with("    }\n}\n\n// Trailing comment that must survive\nfn helper() {}\n")
+    );
+    assert!(syn::parse_file(&content).is_ok());
+}
+
+#[test]
+fn escapes_inputs_containing_raw_string_delimiters() {
+    let (path, mut file) = solution("delimiters", SOLUTION);
+    fs::remove_file(&path).unwrap();
+    let tricky = example("test_part1", "say \"#hi\"# and \"##", "\"#");
+    file.insert_test(&tricky).unwrap();
+
+    assert_eq!(file.tests().unwrap(), [tricky]);
+    assert_eq!(file.test_name(Part::One).unwrap(), "test_part1_2");
+    assert_eq!(file.test_name(Part::Two).unwrap(), "test_part2");
+}
+
+#[test]
+fn creates_test_module_when_missing() {
+    let (path, mut file) = solution("missing", "fn part1(input: String) {}");
+    fs::remove_file(&path).unwrap();
+    file.insert_test(&example("test_part1", "x", "y")).unwrap();
+    assert_eq!(file.tests().unwrap(), [example("test_part1", "x", "y")]);
+}